@@ -0,0 +1,100 @@
+//! Error type of the crate, for the shapes that fail to be created or drawn.
+
+use std::fmt;
+
+/// Something that went wrong while creating or drawing a shape.
+#[derive(Debug)]
+pub enum Error {
+    /// A vertex or instance buffer could not be created
+    VertexBuffer(glium::vertex::BufferCreationError),
+    /// An index buffer could not be created
+    IndexBuffer(glium::index::BufferCreationError),
+    /// A shader could not be preprocessed or compiled, with its log
+    Shader(String),
+    /// An image could not be loaded or uploaded as a texture
+    Texture(String),
+    /// An OBJ model could not be loaded
+    Obj(tobj::LoadError),
+    /// A glTF model could not be loaded
+    Gltf(::gltf::Error),
+    /// The GPU refused to draw
+    Draw(glium::DrawError),
+    /// The GPU can't draw instanced shapes
+    InstancingNotSupported,
+    /// A shape was given parameters it can't be built from
+    InvalidShape(String),
+    /// The passes of a render graph can't be ordered or drawn
+    RenderGraph(String),
+    /// A scene file could not be read or has a line or value that can't be understood
+    Scene(String),
+    /// A star catalog or a constellation file could not be read
+    Catalog(String),
+    /// The window could not be opened, or was given options it can't be opened with
+    Window(String),
+}
+
+/// Result of the operations that can fail with an `Error`.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::VertexBuffer(e) => write!(f, "Could not create a vertex buffer: {e}"),
+            Error::IndexBuffer(e) => write!(f, "Could not create an index buffer: {e}"),
+            Error::Shader(log) => write!(f, "Could not compile a shader: {log}"),
+            Error::Texture(message) => write!(f, "{message}"),
+            Error::Obj(e) => write!(f, "Could not load an OBJ model: {e}"),
+            Error::Gltf(e) => write!(f, "Could not load a glTF model: {e}"),
+            Error::Draw(e) => write!(f, "Could not draw: {e}"),
+            Error::InstancingNotSupported => write!(f, "Instancing is not supported"),
+            Error::InvalidShape(message) => write!(f, "Invalid shape: {message}"),
+            Error::RenderGraph(message) => write!(f, "Invalid render graph: {message}"),
+            Error::Scene(message) => write!(f, "Invalid scene file: {message}"),
+            Error::Catalog(message) => write!(f, "Invalid star catalog: {message}"),
+            Error::Window(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::VertexBuffer(e) => Some(e),
+            Error::IndexBuffer(e) => Some(e),
+            Error::Obj(e) => Some(e),
+            Error::Gltf(e) => Some(e),
+            Error::Draw(e) => Some(e),
+            Error::Shader(_) | Error::Texture(_) | Error::InstancingNotSupported | Error::InvalidShape(_) | Error::RenderGraph(_) | Error::Scene(_) | Error::Catalog(_) | Error::Window(_) => None,
+        }
+    }
+}
+
+impl From<glium::vertex::BufferCreationError> for Error {
+    fn from(e: glium::vertex::BufferCreationError) -> Self {
+        Error::VertexBuffer(e)
+    }
+}
+
+impl From<glium::index::BufferCreationError> for Error {
+    fn from(e: glium::index::BufferCreationError) -> Self {
+        Error::IndexBuffer(e)
+    }
+}
+
+impl From<tobj::LoadError> for Error {
+    fn from(e: tobj::LoadError) -> Self {
+        Error::Obj(e)
+    }
+}
+
+impl From<::gltf::Error> for Error {
+    fn from(e: ::gltf::Error) -> Self {
+        Error::Gltf(e)
+    }
+}
+
+impl From<glium::DrawError> for Error {
+    fn from(e: glium::DrawError) -> Self {
+        Error::Draw(e)
+    }
+}
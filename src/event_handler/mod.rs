@@ -1,6 +1,8 @@
-use glium::glutin::event::{ElementState, Event, VirtualKeyCode, WindowEvent};
+use glium::glutin;
+use glium::glutin::event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent};
 use glium::glutin::event_loop::ControlFlow;
-use crate::{glutin, KeyboardInput};
+use crate::render::post::{PostEffect, ToneMapping};
+use crate::shapes::export::ExportFormat;
 
 /// Struct that handles the events of the window.
 pub struct EventHandler {
@@ -12,11 +14,21 @@ pub struct EventHandler {
     pub direction: [f32; 3],
     pub position: [f32; 3],
     pub up: [f32; 3],
+    /// Export of the scene requested with F9 (glTF) or F10 (OBJ), taken by the render loop
+    pub export: Option<ExportFormat>,
+    /// Exposure of the HDR image, lowered with Z and raised with X
+    pub exposure: f32,
+    /// Tone mapping curve, switched with T
+    pub tone_mapping: ToneMapping,
+    /// Post-processing effect to turn on or off, asked with 1 to 5 and taken by the render loop
+    pub toggle_effect: Option<PostEffect>,
+    /// Shows the lines and names of the constellations, switched with C
+    pub show_constellations: bool,
 }
 
 impl EventHandler {
     pub fn new(grow: f32, tilt: f32, spin: f32, translate_x: f32, translate_y: f32, direction: [f32; 3], position: [f32; 3], up: [f32; 3]) -> Self {
-        EventHandler {grow, tilt, spin, translate_x, translate_y, direction, position, up}
+        EventHandler {grow, tilt, spin, translate_x, translate_y, direction, position, up, export: None, exposure: 1.0, tone_mapping: ToneMapping::Aces, toggle_effect: None, show_constellations: true}
     }
 
     /// Method that handles the keyboard input
@@ -31,6 +43,11 @@ impl EventHandler {
             ref mut direction,
             ref mut position,
             ref mut up,
+            ref mut export,
+            ref mut exposure,
+            ref mut tone_mapping,
+            ref mut toggle_effect,
+            ref mut show_constellations,
         } = self;
 
         match ev {
@@ -47,6 +64,21 @@ impl EventHandler {
                         return;
                     };
                     const STEP: f32 = 0.05;
+                    // Exports and switches only once per key press
+                    if state == ElementState::Pressed {
+                        match virtual_keycode {
+                            VirtualKeyCode::F9 => *export = Some(ExportFormat::Gltf),
+                            VirtualKeyCode::F10 => *export = Some(ExportFormat::Obj),
+                            VirtualKeyCode::T => *tone_mapping = tone_mapping.next(),
+                            VirtualKeyCode::C => *show_constellations = !*show_constellations,
+                            VirtualKeyCode::Key1 => *toggle_effect = Some(PostEffect::ALL[0]),
+                            VirtualKeyCode::Key2 => *toggle_effect = Some(PostEffect::ALL[1]),
+                            VirtualKeyCode::Key3 => *toggle_effect = Some(PostEffect::ALL[2]),
+                            VirtualKeyCode::Key4 => *toggle_effect = Some(PostEffect::ALL[3]),
+                            VirtualKeyCode::Key5 => *toggle_effect = Some(PostEffect::ALL[4]),
+                            _ => (),
+                        }
+                    }
                     /// If the key is pressed, the value is changed
                     if let state = ElementState::Pressed {
                         /// Parses the pressed key and changes the value
@@ -73,6 +105,8 @@ impl EventHandler {
                             VirtualKeyCode::F4 => (*direction)[1] += STEP,
                             VirtualKeyCode::F5 => (*direction)[2] += STEP,
                             VirtualKeyCode::F6 => (*direction)[2] -= STEP,
+                            VirtualKeyCode::Z => *exposure = (*exposure - STEP).max(STEP),
+                            VirtualKeyCode::X => *exposure += STEP,
                             _ => (),
                         }
                     }
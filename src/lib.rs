@@ -0,0 +1,24 @@
+//! Shapes, transforms and shaders of the space scene, drawn with glium.
+//!
+//! Every shape is built from a `MeshBuilder` or its own constructor and drawn
+//! through `Drawable`, placed by a `Transform` in a frame described by a `RenderContext`.
+//! The matrix macros (`translate!`, `rotate!`, `scale!`, `identity!`) and
+//! `load_tex!` are exported at the root of the crate.
+
+#[macro_use]
+extern crate glium;
+
+pub mod error;
+pub mod event_handler;
+pub mod render;
+pub mod scene;
+pub mod shapes;
+pub mod window;
+
+pub use error::{Error, Result};
+pub use event_handler::EventHandler;
+pub use shapes::matrices;
+pub use render::{Drawable, Pass, RenderContext, RenderTarget};
+pub use scene::SceneFile;
+pub use shapes::Transform;
+pub use window::WindowOptions;
@@ -1,129 +1,271 @@
-mod shapes;
-mod event_handler;
+//! Demo scene of the `rt` crate: the earth, the moon, saturn, asteroids, models and the sky.
 
 #[macro_use]
+extern crate rt;
+
 extern crate glium;
 
 extern crate image;
 
-use crate::glutin::event::KeyboardInput;
+use rt::{shapes, matrices, Error, EventHandler, Pass, RenderContext, SceneFile, Transform, WindowOptions};
+use rt::render::{BlendMode, PassTarget, RenderGraph, RenderPass, RenderQueue};
+use rt::render::post::{add_bloom, tone_mapping_pass, PostStackBuilder, BLOOM_TEXTURE};
+use rt::shapes::export::{ExportFormat, SceneExport};
+use rt::shapes::mesh::{Material, Mesh, MeshBuilder};
+use rt::shapes::sky::{Constellation, Constellations, Panorama, Sky, StarCatalog};
 use crate::glutin::event_loop::ControlFlow;
-use crate::shapes::{DynDrawble, StaticDrawble, Transform};
-use glium::backend::glutin::DisplayCreationError;
-use glium::glutin::event::Event;
-use glium::glutin::event_loop::EventLoop;
 use glium::texture::*;
-use glium::{glutin, Display, Surface};
-use glutin::event::ElementState;
-use glutin::event::VirtualKeyCode;
-use glutin::event::WindowEvent;
-use shapes::matrices;
-use std::any::Any;
+use glium::glutin;
 use std::f32::consts::PI;
-use event_handler::EventHandler;
-
-type Light = [f32; 3];
-
-//Starts the window and the event loop
-fn start_opengl(
-    title: &str,
-    mut size: Option<(u32, u32)>,
-) -> (EventLoop<()>, Result<Display, DisplayCreationError>) {
-    let size = size.get_or_insert((400, 400));
-
-    let event_loop = glutin::event_loop::EventLoop::new();
-
-    let window = glutin::window::WindowBuilder::new()
-        .with_title(title)
-        .with_inner_size(glutin::dpi::LogicalSize::new(size.0, size.1));
+use std::rc::Rc;
 
-    let context = glutin::ContextBuilder::new().with_depth_buffer(24);
+/// Gives back the value of a result, or reports what could not be done and quits.
+fn or_exit<T>(result: Result<T, Error>, what: &str) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("Could not {what}: {e}");
+        std::process::exit(1)
+    })
+}
 
-    let display = glium::Display::new(window, context, &event_loop);
-    (event_loop, display)
+/// Keeps the error of a draw call, named after what was drawn.
+fn report(errors: &mut Vec<String>, what: &str, result: Result<(), Error>) {
+    if let Err(e) = result {
+        errors.push(format!("Could not draw {what}: {e}"));
+    }
 }
 
 /// Main function
 fn main() {
-    let (event_loop, display) = match start_opengl("First", None) {
-        (event_loop, Ok(display)) => (event_loop, display),
-        (_, Err(e)) => panic!("Could not create window: {e}"),
+    // The scene file is the first argument if it is not a flag, the flags set up the window
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let scene_path = match args.first() {
+        Some(arg) if !arg.starts_with("--") => args.remove(0),
+        _ => concat!(env!("CARGO_MANIFEST_DIR"), "/scene.cfg").to_string(),
+    };
+    let scene = match SceneFile::load(&scene_path) {
+        Ok(scene) => scene,
+        Err(e) => {
+            eprintln!("{e}, using the default scene");
+            SceneFile::default()
+        }
     };
 
-    //let moon_texture = load_tex!(display, "imgs/2k_venus_surface.jpg", jpeg);
-    /// Loads the earth texture
-    let earth_texture = load_tex!(display, "imgs/2k_earth_daymap.jpg", jpeg);
+    let options = or_exit(WindowOptions::from_scene(&scene).and_then(|options| options.with_args(args)), "read the window options");
+    let event_loop = glutin::event_loop::EventLoop::new();
+    let (display, samples) = or_exit(options.open(&event_loop), "open the window");
+    if samples < options.samples {
+        eprintln!("The window got {samples}x multisampling instead of {}x", options.samples);
+    }
+
+    // Loads the earth texture
+    let earth_texture = or_exit(load_tex!(display, "imgs/2k_earth_daymap.jpg", jpeg), "load the earth texture");
 
-    /// Initializes the earth object
-    let earth = shapes::sphere::SphereBuilder::new()
+    // Initializes the earth object
+    let earth = or_exit(shapes::sphere::SphereBuilder::new()
         .radius(1.0)
         .texture(earth_texture)
-        .build(&display);
+        .build(&display), "create the earth");
+
+    // Initializes the atmosphere of the earth, seen through
+    let atmosphere = {
+        let (vertices, indices) = shapes::sphere::SphereBuilder::new().radius(1.06).geometry();
+        let material = Material {
+            base_color: [0.35, 0.6, 1.0, 0.25],
+            blend: Some(BlendMode::Alpha),
+            ..Material::default()
+        };
+        or_exit(Mesh::new(&display, &vertices, &indices, Rc::new(material)), "create the atmosphere")
+    };
 
-    /// Initializes the moon object
-    let moon = shapes::sphere::SphereBuilder::new()
+    // Initializes the moon object
+    let moon = or_exit(shapes::sphere::SphereBuilder::new()
         .radius(0.1)
-        // .texture(moon_texture)
         .color([0.5; 3])
-        .build(&display);
+        .build(&display), "create the moon");
 
-    /// Initializes the saturn object
-    let saturn = shapes::ring_planet::RingPlanet::new(
-        &display,
-        1.3,
+    // Initializes the saturn object
+    let saturn = or_exit(
         shapes::sphere::SphereBuilder::new()
             .radius(1.0)
             .color([0.0, 0.3, 0.7])
-            .build(&display),
+            .build(&display)
+            .and_then(|planet| shapes::ring_planet::RingPlanet::new(&display, 1.3, planet)),
+        "create saturn",
     );
 
-    ///Initalizes the asteroid object
-    let asteroid = shapes::cube::CubeBuilder::new()
-        .size(0.5)
-        .color([0.2; 3])
-        .build(&display);
+    // Initializes the asteroid object
+    let asteroid = or_exit(shapes::asteroid::AsteroidBuilder::new()
+        .seed(7)
+        .radius(0.5)
+        .build(&display), "create the asteroid");
+
+    // Initializes the asteroid belt
+    let asteroid_belt = or_exit(shapes::asteroid_belt::AsteroidBeltBuilder::new()
+        .seed(3)
+        .radii(1.1, 1.4)
+        .build(&display), "create the asteroid belt");
+
+    // Keeps the textures of the loaded models
+    let mut textures = shapes::textures::TextureManager::new();
 
-    /// Initializes the sky
-    let mut sky = shapes::sky::Sky::new(&display);
+    // Loads the shuttle model
+    let shuttle = or_exit(shapes::model::Model::from_obj(
+        &display,
+        &mut textures,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/models/shuttle.obj"),
+    ), "load the shuttle");
 
-    let star = shapes::star::Star::new(&display);
+    // Loads the space station model
+    let station = or_exit(shapes::model::Model::from_gltf(
+        &display,
+        &mut textures,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/models/station.gltf"),
+    ), "load the space station");
+    // What the loaders left out of the models
+    for warning in shuttle.warnings().iter().chain(station.warnings()) {
+        eprintln!("{warning}");
+    }
+
+    // Assembles an outpost from the procedural primitives
+    let outpost = {
+        use shapes::model::{Model, Node};
+        let hub = shapes::cylinder::CylinderBuilder::new().radius(0.15).height(0.8).color([0.7, 0.7, 0.75]);
+        let ring = shapes::torus::TorusBuilder::new().radius(0.6).tube_radius(0.05).color([0.8, 0.8, 0.8]);
+        let dock = shapes::cone::ConeBuilder::new().radius(0.15).top_radius(0.06).height(0.2).color([0.6, 0.6, 0.65]);
+        let port = shapes::disc::DiscBuilder::new().radius(0.06).inner_radius(0.03).color([0.9, 0.5, 0.1]);
+        let tank = shapes::capsule::CapsuleBuilder::new().radius(0.08).height(0.2).color([0.9, 0.9, 0.9]);
+        let panel = shapes::plane::PlaneBuilder::new().size(0.6, 0.25).subdivisions(4, 1).color([0.1, 0.2, 0.6]);
+
+        let node = |name: &str, matrix, meshes| Node { name: name.to_string(), matrix, meshes, children: Vec::new() };
+        let meshes = [hub.build(&display), ring.build(&display), dock.build(&display), port.build(&display), tank.build(&display), panel.build(&display)];
+        Model::new(
+            or_exit(meshes.into_iter().collect(), "create the outpost"),
+            vec![
+                node("hub", identity!(), vec![0, 1]),
+                node("dock", translate!(0.0, 0.5, 0.0), vec![2]),
+                node("port", translate!(0.0, 0.601, 0.0), vec![3]),
+                node("tank", translate!(0.0, -0.55, 0.0), vec![4]),
+                node("left panel", translate!(-1.0, 0.0, 0.0), vec![5]),
+                node("right panel", translate!(1.0, 0.0, 0.0), vec![5]),
+            ],
+        )
+    };
 
-    /// Defining the draw parameters
+    // Stars and panorama of the sky from the scene file, with the paths relative to it
+    let scene_dir = std::path::Path::new(&scene_path).parent().map(|dir| dir.to_path_buf()).unwrap_or_default();
+    let catalog = match scene.get("sky.catalog") {
+        Some(path) => StarCatalog::load(scene_dir.join(path)),
+        None => StarCatalog::bright_stars(),
+    };
+    let catalog = or_exit(catalog, "read the star catalog");
+    let sky = or_exit(Sky::with_catalog(&display, &catalog, 7), "create the sky");
+    let twinkle = or_exit(scene.value::<f32>("sky.twinkle"), "read the twinkle of the stars").unwrap_or(sky.twinkle);
+    let twinkle_speed = or_exit(scene.value::<f32>("sky.twinkle_speed"), "read the twinkle of the stars").unwrap_or(sky.twinkle_speed);
+    let sky = sky.with_twinkle(twinkle, twinkle_speed);
+    let panorama = if let Some(path) = scene.get("sky.panorama") {
+        Some(Panorama::equirectangular(&display, scene_dir.join(path)))
+    } else {
+        scene.list("sky.cubemap").map(|faces| {
+            let faces: Vec<_> = faces.iter().map(|face| scene_dir.join(face)).collect();
+            let faces: [_; 6] = faces.try_into()
+                .map_err(|_| Error::Scene("sky.cubemap needs six faces: +X, -X, +Y, -Y, +Z, -Z".to_string()))?;
+            Panorama::cubemap(&display, faces)
+        })
+    };
+    let sky = match panorama {
+        Some(panorama) => sky.with_panorama(or_exit(panorama, "load the panorama of the sky")),
+        None => sky,
+    };
+
+    // Constellations over the stars, without the figures whose stars are not in the catalog
+    let constellations = match scene.get("sky.constellations") {
+        Some(path) => Constellation::load(scene_dir.join(path), &catalog),
+        None => Constellation::bundled(&catalog),
+    };
+    let constellations = constellations.and_then(|(constellations, skipped)| {
+        for skipped in skipped {
+            eprintln!("{skipped}");
+        }
+        Constellations::new(&display, &constellations)
+    });
+    let constellations = match constellations {
+        Ok(constellations) => Some(constellations),
+        Err(e) => {
+            eprintln!("{e}, drawing the sky without the constellations");
+            None
+        }
+    };
+
+    let star = or_exit(shapes::star::Star::new(&display), "create the star");
+
+    // Defining the draw parameters
     let draw_params = glium::draw_parameters::DrawParameters {
         depth: glium::Depth {
             test: glium::DepthTest::IfLess,
             write: true,
-            range: (0.0, 0.9),
             ..Default::default()
         },
         // backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
         ..Default::default()
     };
 
-    /// Render runtime
+    // Passes of the frame: the sky behind everything, the scene and what is seen through, then the star over it,
+    // all drawn in HDR so the brightest colors glow through the bloom before the tone mapping,
+    // and the post-processing effects of the scene file over the tone mapped image
+    let hdr = PassTarget::Color { color: "hdr", depth: Some("hdr_depth") };
+    let graph = RenderGraph::new()
+        .samples(samples as u32)
+        .color_texture("hdr", UncompressedFloatFormat::F16F16F16F16)
+        .depth_texture("hdr_depth")
+        .pass(RenderPass::new("sky", Pass::Sky).target(hdr).clear_color((0., 0., 0., 1.)).clear_depth(1.))
+        .pass(RenderPass::new("opaque", Pass::Opaque).target(hdr).params(draw_params.clone()))
+        .pass(RenderPass::new("transparent", Pass::Transparent).target(hdr))
+        .pass(RenderPass::new("overlay", Pass::Overlay).target(hdr).clear_depth(1.).params(draw_params));
+    let graph = or_exit(add_bloom(graph, &display, "hdr", 1.0), "create the bloom");
+    let (tone_mapping, tone_mapping_settings) = or_exit(tone_mapping_pass(&display, "hdr", Some(BLOOM_TEXTURE)), "create the tone mapping");
+    let graph = graph
+        .color_texture("ldr", UncompressedFloatFormat::U8U8U8U8)
+        .pass(tone_mapping.target(PassTarget::Color { color: "ldr", depth: None }));
+    let (graph, post_stack) = or_exit(
+        PostStackBuilder::from_scene(&scene).and_then(|stack| stack.build(graph, &display, "ldr")),
+        "create the post-processing effects",
+    );
+    let mut graph = or_exit(graph.build(), "build the render graph");
+
+    // Render runtime
     let mut angle = (0..360)
         .map(|i| (i as f32).to_radians())
         .cycle();
 
-    let mut size = (0..240) // frames
-        .map(|i| (i - 120) as f32 * 0.3 / 240.0 + 0.4)
-        .cycle();
+    // Shader programs of the display, reloaded from their files with the `hot-reload` feature
+    let programs = shapes::programs::ProgramCache::for_display(&display);
 
-    /// Initializes the event handler
+    // Initializes the event handler
     let mut event_handler = EventHandler::new(0.15, 0.4, PI, 0., 0., [0.0, 0.0, 1.0], [-2.0, 1.0, 1.0], [0.0, 1.0, 0.0]);
 
+    // Errors of the last frame, so an error repeated every frame is reported once
+    let mut last_errors = Vec::new();
+
+    let start = std::time::Instant::now();
+
     event_loop.run(move |ev, _, cf| {
         let a = angle.next().unwrap();
-        let s = size.next().unwrap();
-        let mut target = display.draw();
-        target.clear_color_and_depth((0., 0., 0., 1.), 1.);
+        let mut frame = display.draw();
 
         set_wait(cf, 16_666_667);
 
-        /// Handles the events
+        // Handles the events
         event_handler.handle_event(ev, cf);
 
-        let perspective = matrices::perspective_matrix(&mut target);
+        for reloaded in programs.reload() {
+            match reloaded {
+                Ok(name) => println!("Reloaded {name}"),
+                Err(e) => eprintln!("{e}"),
+            }
+        }
+
+        let perspective = matrices::perspective_matrix(&frame);
+        let export = event_handler.export.take();
         let EventHandler {
             grow,
             tilt,
@@ -132,72 +274,139 @@ fn main() {
             translate_y,
             direction,
             position,
-            up
+            up,
+            ..
         } = event_handler;
 
-        /// Draws the earth
-        earth.draw(
-            &mut target,
-            &draw_params,
-            Transform {
-                rotate_self: [0.0, spin, 0.0],
-                scale: 0.3,
-                view: [direction, position, up],
-                ..Default::default()
-            },
-        );
-
-        /// Draws the moon
-        moon.draw(
-            &mut target,
-            &draw_params,
-            Transform {
-                translation: [-0.8, 0.0, 0.0],
-                rotate_self: [0.0, a, 0.0],
-                rotation: [0.0, a, a.cos() * tilt],
-                view: [direction, position, up],
-                ..Default::default()
-            },
-        );
-
-        /// Draws saturn
-        saturn.draw(
-            &mut target,
-            &draw_params,
-            Transform {
-                translation: [-0.7, 0.7, 0.0],
-                scale: grow,
-                rotate_self: [0.0, -a, -0.4],
-                view: [direction, position, up],
-                ..Default::default()
-            },
-        );
-
-        /// Draws the asteroid
-        asteroid.draw(
-            &mut target,
-            &draw_params,
-            Transform {
-                translation: [0.5 + translate_x, 0.5 + translate_y, 0.5],
-                rotate_self: [0.0, a, 0.2],
-                scale: 0.25,
-                view: [direction, position, up],
-                ..Default::default()
-            },
-        );
-
-        /// Draws the sky
-        sky.draw(&mut target, &draw_params);
-
-        /// Draws the star
-        star.draw(&mut target, &draw_params, Transform {
+        let earth_transform = Transform {
+            rotate_self: [0.0, spin, 0.0],
+            scale: 0.3,
+            view: [direction, position, up],
+            ..Default::default()
+        };
+        let moon_transform = Transform {
+            translation: [-0.8, 0.0, 0.0],
+            rotate_self: [0.0, a, 0.0],
+            rotation: [0.0, a, a.cos() * tilt],
+            view: [direction, position, up],
+            ..Default::default()
+        };
+        let saturn_transform = Transform {
+            translation: [-0.7, 0.7, 0.0],
+            scale: grow,
+            rotate_self: [0.0, -a, -0.4],
+            view: [direction, position, up],
+            ..Default::default()
+        };
+        let asteroid_transform = Transform {
+            translation: [0.5 + translate_x, 0.5 + translate_y, 0.5],
+            rotate_self: [0.0, a, 0.2],
+            scale: 0.25,
+            view: [direction, position, up],
+            ..Default::default()
+        };
+
+        // Exports the generated shapes as they are placed in this frame
+        if let Some(format) = export {
+            let mut scene = SceneExport::new();
+            scene.add("earth", &earth, &earth_transform);
+            scene.add("moon", &moon, &moon_transform);
+            scene.add("saturn", &saturn, &saturn_transform);
+            scene.add("asteroid", &asteroid, &asteroid_transform);
+
+            let path = match format {
+                ExportFormat::Gltf => "scene.gltf",
+                ExportFormat::Obj => "scene.obj",
+            };
+            match scene.write(path, format) {
+                Ok(skipped) => {
+                    for skipped in skipped {
+                        eprintln!("{skipped}");
+                    }
+                    println!("Exported the scene to {path}");
+                }
+                Err(e) => eprintln!("Could not export the scene: {e}"),
+            }
+        }
+
+        let context = RenderContext {
+            camera: [direction, position, up],
+            projection: perspective,
+            light_direction: shapes::LIGHT_DIRECTION,
+            time: start.elapsed().as_secs_f32(),
+            pass: Pass::Opaque,
+            params: Default::default(),
+            resources: None,
+        };
+
+        let mut queue = RenderQueue::new();
+        queue.push(Pass::Opaque, "the earth", &earth, earth_transform.clone());
+        queue.push(Pass::Transparent, "the atmosphere", &atmosphere, earth_transform);
+        queue.push(Pass::Opaque, "the moon", &moon, moon_transform);
+        queue.push(Pass::Opaque, "saturn", &saturn, saturn_transform);
+        queue.push(Pass::Opaque, "the asteroid", &asteroid, asteroid_transform);
+        queue.push(Pass::Opaque, "the asteroid belt", &asteroid_belt, Transform {
+            rotate_self: [0.3, a / 4.0, 0.0],
+            scale: 0.6,
+            view: context.camera,
+            ..Default::default()
+        });
+        queue.push(Pass::Opaque, "the shuttle", &shuttle, Transform {
+            translation: [0.3, -0.6, 0.3],
+            rotate_self: [0.2, -a, 0.0],
+            scale: 0.2,
+            view: context.camera,
+            ..Default::default()
+        });
+        queue.push(Pass::Opaque, "the space station", &station, Transform {
+            translation: [0.75, 0.15, 0.3],
+            rotate_self: [0.4, a / 2.0, 0.0],
+            scale: 0.12,
+            view: context.camera,
+            ..Default::default()
+        });
+        queue.push(Pass::Opaque, "the outpost", &outpost, Transform {
+            translation: [-0.4, -0.65, 0.3],
+            rotate_self: [0.5, a / 3.0, 0.0],
+            scale: 0.25,
+            view: context.camera,
+            ..Default::default()
+        });
+        queue.push(Pass::Sky, "the sky", &sky, Transform::default());
+        if let Some(constellations) = constellations.as_ref().filter(|_| event_handler.show_constellations) {
+            queue.push(Pass::Sky, "the constellations", constellations, Transform::default());
+        }
+        queue.push(Pass::Overlay, "the star", &star, Transform {
             translation: [0.8, -0.5, 0.0],
             rotate_self: [0.0, 0.0, a/6.0],
             scale: 0.3,
             ..Default::default()
         });
 
-        target.finish().unwrap();
+        if let Some(effect) = event_handler.toggle_effect.take() {
+            match post_stack.toggle(effect) {
+                Ok(enabled) => println!("{} {}", effect.name(), if enabled { "on" } else { "off" }),
+                Err(e) => eprintln!("{e}"),
+            }
+        }
+        tone_mapping_settings.set("exposure", event_handler.exposure);
+        tone_mapping_settings.set("operator", event_handler.tone_mapping.id());
+
+        let mut errors = Vec::new();
+        if let Err(e) = graph.render(&display, &mut frame, &context, &queue, |what, result| report(&mut errors, what, result)) {
+            errors.push(format!("Could not draw the frame: {e}"));
+        }
+
+        if let Err(e) = frame.finish() {
+            errors.push(format!("Could not show the frame: {e}"));
+        }
+
+        if errors != last_errors {
+            for e in &errors {
+                eprintln!("{e}");
+            }
+            last_errors = errors;
+        }
     })
 }
 
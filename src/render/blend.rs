@@ -0,0 +1,31 @@
+use glium::{Blend, BlendingFunction, LinearBlendingFactor};
+
+/// How a transparent object is mixed with what is already drawn behind it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Covers the background by the alpha of the color, for glass or atmospheres
+    Alpha,
+    /// Adds the color, weighted by its alpha, to the background, for glows and particles
+    Additive,
+    /// Like `Alpha`, for colors already multiplied by their alpha
+    Premultiplied,
+}
+
+impl BlendMode {
+    pub fn blend(self) -> Blend {
+        let (source, destination) = match self {
+            BlendMode::Alpha => (LinearBlendingFactor::SourceAlpha, LinearBlendingFactor::OneMinusSourceAlpha),
+            BlendMode::Additive => (LinearBlendingFactor::SourceAlpha, LinearBlendingFactor::One),
+            BlendMode::Premultiplied => (LinearBlendingFactor::One, LinearBlendingFactor::OneMinusSourceAlpha),
+        };
+        Blend {
+            color: BlendingFunction::Addition { source, destination },
+            // The alpha of the target keeps how much of it is covered, whatever the mode
+            alpha: BlendingFunction::Addition {
+                source: LinearBlendingFactor::One,
+                destination: LinearBlendingFactor::OneMinusSourceAlpha,
+            },
+            constant_value: (0.0, 0.0, 0.0, 0.0),
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::texture::{DepthFormat, DepthTexture2d, DepthTexture2dMultisample, MipmapsOption, Texture2d, Texture2dMultisample, TextureCreationError, UncompressedFloatFormat};
+use glium::uniforms::MagnifySamplerFilter;
+use glium::{BlitMask, BlitTarget, Display, Frame, Rect, Surface};
+
+use crate::error::{Error, Result};
+use crate::render::{Pass, PassTarget, RenderContext, RenderPass, RenderQueue, RenderTarget};
+use crate::shapes::Transform;
+
+/// Kind of a texture declared in the graph.
+#[derive(Debug, Clone, Copy)]
+enum TextureKind {
+    Color(UncompressedFloatFormat),
+    Depth,
+}
+
+/// Textures the passes of a graph draw into, as big as the window or a fraction of it.
+#[derive(Default)]
+pub struct Resources {
+    size: (u32, u32),
+    color: HashMap<&'static str, Texture2d>,
+    depth: HashMap<&'static str, DepthTexture2d>,
+    /// Multisampled copies of the textures drawn into with a depth buffer, with multisampling
+    multisampled_color: HashMap<&'static str, Texture2dMultisample>,
+    multisampled_depth: HashMap<&'static str, DepthTexture2dMultisample>,
+}
+
+impl Resources {
+    pub fn color(&self, name: &str) -> Option<&Texture2d> {
+        self.color.get(name)
+    }
+
+    pub fn depth(&self, name: &str) -> Option<&DepthTexture2d> {
+        self.depth.get(name)
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+}
+
+/// Passes of a frame and the textures they share, run in the order their dependencies require.
+///
+/// A pass drawing into a texture runs before the passes reading it,
+/// otherwise the passes keep the order they were added in.
+///
+/// # Example
+/// ```
+/// use glium::texture::UncompressedFloatFormat;
+/// use rt::Pass;
+/// use rt::render::{PassTarget, RenderGraph, RenderPass};
+/// let graph = RenderGraph::new()
+///  .color_texture("scene", UncompressedFloatFormat::U8U8U8U8)
+///  .depth_texture("scene_depth")
+///  .pass(RenderPass::new("overlay", Pass::Overlay).reads("scene"))
+///  .pass(RenderPass::new("opaque", Pass::Opaque).target(PassTarget::Color { color: "scene", depth: Some("scene_depth") }))
+///  .build()
+///  .unwrap();
+/// assert_eq!(graph.order().collect::<Vec<_>>(), ["opaque", "overlay"]);
+/// ```
+#[derive(Default)]
+pub struct RenderGraph {
+    passes: Vec<RenderPass>,
+    /// Textures with the number the size of the window is divided by
+    textures: Vec<(&'static str, TextureKind, u32)>,
+    /// Indices of the passes in the order they run
+    order: Vec<usize>,
+    /// Samples of the multisampled textures, none below 2
+    samples: u32,
+    resources: Resources,
+}
+
+impl RenderGraph {
+    pub fn new() -> Self {
+        RenderGraph::default()
+    }
+
+    /// Declares a color texture the passes can draw into and read.
+    pub fn color_texture(self, name: &'static str, format: UncompressedFloatFormat) -> Self {
+        self.scaled_color_texture(name, format, 1)
+    }
+
+    /// Declares a color texture with the size of the window divided by `divisor`,
+    /// for effects working on a smaller image such as the bloom.
+    pub fn scaled_color_texture(mut self, name: &'static str, format: UncompressedFloatFormat, divisor: u32) -> Self {
+        self.textures.push((name, TextureKind::Color(format), divisor.max(1)));
+        self
+    }
+
+    /// Declares a depth texture the passes can draw into and read.
+    pub fn depth_texture(mut self, name: &'static str) -> Self {
+        self.textures.push((name, TextureKind::Depth, 1));
+        self
+    }
+
+    /// Draws the passes with both a color and a depth target into multisampled textures,
+    /// copied into the color and depth textures after each pass so the next passes read them smoothed.
+    /// Uses fewer samples if the GPU can't make the textures with that many,
+    /// reporting why under the name `multisampling` when rendering.
+    pub fn samples(mut self, samples: u32) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    pub fn pass(mut self, pass: RenderPass) -> Self {
+        self.passes.push(pass);
+        self
+    }
+
+    /// Checks the textures used by the passes and orders the passes.
+    pub fn build(mut self) -> Result<Self> {
+        let kind = |name: &str| self.textures.iter().find(|(texture, _, _)| *texture == name).map(|(_, kind, _)| *kind);
+        for pass in &self.passes {
+            let expected = match pass.target {
+                PassTarget::Screen => Vec::new(),
+                PassTarget::Color { color, depth } => {
+                    std::iter::once((color, false)).chain(depth.map(|depth| (depth, true))).collect()
+                }
+                PassTarget::Depth(depth) => vec![(depth, true)],
+            };
+            for (name, is_depth) in expected {
+                match (kind(name), is_depth) {
+                    (Some(TextureKind::Color(_)), false) | (Some(TextureKind::Depth), true) => (),
+                    (None, _) => return Err(Error::RenderGraph(format!("pass {} draws into the unknown texture {name}", pass.name))),
+                    _ => return Err(Error::RenderGraph(format!("pass {} draws into {name}, which is not a {} texture", pass.name, if is_depth { "depth" } else { "color" }))),
+                }
+            }
+            if let Some(name) = pass.reads.iter().find(|name| kind(name).is_none()) {
+                return Err(Error::RenderGraph(format!("pass {} reads the unknown texture {name}", pass.name)));
+            }
+        }
+
+        self.order = self.sort()?;
+        Ok(self)
+    }
+
+    /// Names of the passes in the order they run.
+    pub fn order(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.order.iter().map(|&pass| self.passes[pass].name)
+    }
+
+    /// Orders the passes so each one runs after the passes drawing into what it reads,
+    /// and after the passes added before it drawing into the same target.
+    fn sort(&self) -> Result<Vec<usize>> {
+        let target_key = |pass: &RenderPass| match pass.target {
+            PassTarget::Screen => "",
+            PassTarget::Color { color, .. } => color,
+            PassTarget::Depth(depth) => depth,
+        };
+        let dependencies: Vec<Vec<usize>> = self.passes.iter()
+            .enumerate()
+            .map(|(i, pass)| {
+                self.passes.iter()
+                    .enumerate()
+                    .filter(|&(j, other)| {
+                        j != i && (
+                            other.writes().iter().any(|texture| pass.reads.contains(texture))
+                            || (j < i && target_key(other) == target_key(pass))
+                        )
+                    })
+                    .map(|(j, _)| j)
+                    .collect()
+            })
+            .collect();
+
+        let mut order = Vec::with_capacity(self.passes.len());
+        while order.len() < self.passes.len() {
+            let next = (0..self.passes.len())
+                .find(|i| !order.contains(i) && dependencies[*i].iter().all(|dependency| order.contains(dependency)));
+            match next {
+                Some(next) => order.push(next),
+                None => {
+                    let stuck: Vec<&str> = (0..self.passes.len())
+                        .filter(|i| !order.contains(i))
+                        .map(|i| self.passes[i].name)
+                        .collect();
+                    return Err(Error::RenderGraph(format!("passes {} depend on each other", stuck.join(", "))));
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Creates the textures again when the window changed size,
+    /// giving back the errors of the multisampled textures the GPU refused.
+    fn resize(&mut self, display: &Display, size: (u32, u32)) -> Result<Vec<Error>> {
+        if self.resources.size == size {
+            return Ok(Vec::new());
+        }
+
+        let mut resources = Resources { size, ..Default::default() };
+        for &(name, kind, divisor) in &self.textures {
+            let (width, height) = ((size.0 / divisor).max(1), (size.1 / divisor).max(1));
+            let error = |e| Error::Texture(format!("Could not create the texture {name} of the render graph: {e}"));
+            match kind {
+                TextureKind::Color(format) => {
+                    let texture = Texture2d::empty_with_format(display, format, MipmapsOption::NoMipmap, width, height).map_err(error)?;
+                    resources.color.insert(name, texture);
+                }
+                TextureKind::Depth => {
+                    let texture = DepthTexture2d::empty_with_format(display, DepthFormat::I24, MipmapsOption::NoMipmap, width, height).map_err(error)?;
+                    resources.depth.insert(name, texture);
+                }
+            }
+        }
+        let refused = self.resize_multisampled(display, &mut resources);
+        self.resources = resources;
+        Ok(refused)
+    }
+
+    /// Creates the multisampled textures of the passes drawing with a depth buffer,
+    /// halving the samples until the GPU can make them or there are too few to be worth it.
+    /// Gives back why each number of samples tried was refused.
+    fn resize_multisampled(&mut self, display: &Display, resources: &mut Resources) -> Vec<Error> {
+        let mut refused = Vec::new();
+        let (width, height) = resources.size;
+        let targets: Vec<(&'static str, &'static str)> = self.passes.iter()
+            .filter_map(|pass| match pass.target {
+                PassTarget::Color { color, depth: Some(depth) } => Some((color, depth)),
+                _ => None,
+            })
+            .collect();
+
+        while self.samples > 1 {
+            let created: std::result::Result<Vec<_>, TextureCreationError> = targets.iter()
+                .map(|&(color, depth)| {
+                    let format = self.textures.iter()
+                        .find_map(|&(name, kind, _)| match kind {
+                            TextureKind::Color(format) if name == color => Some(format),
+                            _ => None,
+                        })
+                        .unwrap_or(UncompressedFloatFormat::U8U8U8U8);
+                    let color_texture = Texture2dMultisample::empty_with_format(display, format, MipmapsOption::NoMipmap, width, height, self.samples)?;
+                    let depth_texture = DepthTexture2dMultisample::empty_with_format(display, DepthFormat::I24, MipmapsOption::NoMipmap, width, height, self.samples)?;
+                    Ok((color, color_texture, depth, depth_texture))
+                })
+                .collect();
+            match created {
+                Ok(created) => {
+                    for (color, color_texture, depth, depth_texture) in created {
+                        resources.multisampled_color.insert(color, color_texture);
+                        resources.multisampled_depth.insert(depth, depth_texture);
+                    }
+                    return refused;
+                }
+                Err(e) => {
+                    refused.push(Error::Texture(format!("Could not draw with {}x multisampling ({e}), trying {}x", self.samples, self.samples / 2)));
+                    self.samples /= 2;
+                }
+            }
+        }
+        refused
+    }
+
+    /// Runs the passes, each drawing the objects queued for its kind.
+    ///
+    /// The errors of the objects are given to `report` with their names, without stopping the frame.
+    /// An error is returned only when the graph can't run, such as a texture that can't be created.
+    pub fn render(
+        &mut self,
+        display: &Display,
+        frame: &mut Frame,
+        context: &RenderContext,
+        queue: &RenderQueue,
+        mut report: impl FnMut(&str, Result<()>),
+    ) -> Result<()> {
+        let size = frame.get_dimensions();
+        // A minimized window has nothing to draw into
+        if size.0 == 0 || size.1 == 0 {
+            return Ok(());
+        }
+        for error in self.resize(display, size)? {
+            report("multisampling", Err(error));
+        }
+
+        let resources = &self.resources;
+        for &index in &self.order {
+            let pass = &self.passes[index];
+            let context = RenderContext {
+                pass: pass.kind,
+                params: pass.params.clone(),
+                resources: Some(resources),
+                ..context.clone()
+            };
+            let framebuffer_error = |e| Error::RenderGraph(format!("Could not draw pass {} into its textures: {e:?}", pass.name));
+
+            match pass.target {
+                PassTarget::Screen => draw_pass(pass, &mut RenderTarget::from(&mut *frame), &context, queue, &mut report),
+                PassTarget::Color { color, depth: Some(depth) } if resources.multisampled_color.contains_key(color) => {
+                    let mut framebuffer = SimpleFrameBuffer::with_depth_buffer(
+                        display,
+                        &resources.multisampled_color[color],
+                        &resources.multisampled_depth[depth],
+                    ).map_err(framebuffer_error)?;
+                    draw_pass(pass, &mut RenderTarget::from(&mut framebuffer), &context, queue, &mut report);
+
+                    // Both the color and the depth are resolved, for the passes reading either
+                    let resolved = SimpleFrameBuffer::with_depth_buffer(display, &resources.color[color], &resources.depth[depth])
+                        .map_err(framebuffer_error)?;
+                    let (width, height) = resolved.get_dimensions();
+                    let source = Rect { left: 0, bottom: 0, width, height };
+                    let whole = BlitTarget { left: 0, bottom: 0, width: width as i32, height: height as i32 };
+                    resolved.blit_buffers_from_simple_framebuffer(&framebuffer, &source, &whole, MagnifySamplerFilter::Nearest, BlitMask::color_and_depth());
+                }
+                PassTarget::Color { color, depth } => {
+                    let color = &resources.color[color];
+                    let mut framebuffer = match depth {
+                        Some(depth) => SimpleFrameBuffer::with_depth_buffer(display, color, &resources.depth[depth]),
+                        None => SimpleFrameBuffer::new(display, color),
+                    }.map_err(framebuffer_error)?;
+                    draw_pass(pass, &mut RenderTarget::from(&mut framebuffer), &context, queue, &mut report);
+                }
+                PassTarget::Depth(depth) => {
+                    let mut framebuffer = SimpleFrameBuffer::depth_only(display, &resources.depth[depth]).map_err(framebuffer_error)?;
+                    draw_pass(pass, &mut RenderTarget::from(&mut framebuffer), &context, queue, &mut report);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Clears the target of a pass as asked and draws its effect, then its objects.
+fn draw_pass(
+    pass: &RenderPass,
+    target: &mut RenderTarget,
+    context: &RenderContext,
+    queue: &RenderQueue,
+    report: &mut impl FnMut(&str, Result<()>),
+) {
+    if pass.clear_color.is_some() || pass.clear_depth.is_some() {
+        target.clear(None, pass.clear_color, false, pass.clear_depth, None);
+    }
+    if let Some(effect) = &pass.effect {
+        report(pass.name, effect.draw(target, context, &Transform::default()));
+    }
+    let objects = match pass.kind {
+        Pass::Transparent => queue.back_to_front(pass.kind),
+        _ => queue.pass(pass.kind).collect(),
+    };
+    for queued in objects {
+        report(queued.name, queued.object.draw(target, context, &queued.transform));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(name: &'static str) -> PassTarget {
+        PassTarget::Color { color: name, depth: None }
+    }
+
+    fn graph() -> RenderGraph {
+        RenderGraph::new()
+            .color_texture("a", UncompressedFloatFormat::U8U8U8U8)
+            .color_texture("b", UncompressedFloatFormat::U8U8U8U8)
+    }
+
+    fn message(result: Result<RenderGraph>) -> String {
+        match result {
+            Err(Error::RenderGraph(message)) => message,
+            Err(e) => panic!("unexpected error {e}"),
+            Ok(graph) => panic!("the graph should not build, its order is {:?}", graph.order().collect::<Vec<_>>()),
+        }
+    }
+
+    #[test]
+    fn passes_depending_on_each_other_are_an_error() {
+        let message = message(graph()
+            .pass(RenderPass::new("first", Pass::Opaque).target(color("a")).reads("b"))
+            .pass(RenderPass::new("second", Pass::Overlay).target(color("b")).reads("a"))
+            .build());
+        assert_eq!(message, "passes first, second depend on each other");
+    }
+
+    #[test]
+    fn passes_drawing_into_the_same_target_keep_their_order() {
+        let graph = graph()
+            .pass(RenderPass::new("sky", Pass::Sky).target(color("a")))
+            .pass(RenderPass::new("screen", Pass::Overlay).reads("a"))
+            .pass(RenderPass::new("opaque", Pass::Opaque).target(color("a")))
+            .pass(RenderPass::new("transparent", Pass::Transparent).target(color("a")))
+            .build()
+            .unwrap();
+        assert_eq!(graph.order().collect::<Vec<_>>(), ["sky", "opaque", "transparent", "screen"]);
+    }
+
+    #[test]
+    fn reading_an_unknown_texture_is_an_error() {
+        let message = message(graph()
+            .pass(RenderPass::new("overlay", Pass::Overlay).reads("missing"))
+            .build());
+        assert_eq!(message, "pass overlay reads the unknown texture missing");
+    }
+
+    #[test]
+    fn drawing_into_an_unknown_texture_is_an_error() {
+        let message = message(graph()
+            .pass(RenderPass::new("opaque", Pass::Opaque).target(color("missing")))
+            .build());
+        assert_eq!(message, "pass opaque draws into the unknown texture missing");
+    }
+}
@@ -0,0 +1,76 @@
+//! What every object drawn in the scene implements, and what it is given to draw itself.
+
+use glium::DrawParameters;
+
+use crate::error::Result;
+use crate::shapes::Transform;
+
+pub use self::blend::BlendMode;
+pub use self::graph::{RenderGraph, Resources};
+pub use self::pass::{PassTarget, RenderPass};
+pub use self::queue::{QueuedObject, RenderQueue};
+pub use self::target::RenderTarget;
+
+mod blend;
+mod graph;
+mod pass;
+pub mod post;
+mod queue;
+mod target;
+
+/// Kind of pass an object is drawn in, so it can skip the passes it takes no part in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pass {
+    /// Depth only, seen from the light
+    Shadow,
+    /// Solid objects of the scene
+    Opaque,
+    /// Background behind everything else
+    Sky,
+    /// Objects seen through, drawn over the opaque ones
+    Transparent,
+    /// Effects applied to the whole image, reading what the other passes drew
+    PostProcess,
+    /// Flat objects over the scene, such as the star
+    Overlay,
+}
+
+/// State of the frame shared by every object drawn in it.
+#[derive(Clone)]
+pub struct RenderContext<'a> {
+    /// Camera in [direction, position, up], like the view of a `Transform`
+    pub camera: [[f32; 3]; 3],
+    pub projection: [[f32; 4]; 4],
+    /// Direction the light comes from
+    pub light_direction: [f32; 3],
+    /// Seconds since the scene started, for the animated objects
+    pub time: f32,
+    pub pass: Pass,
+    pub params: DrawParameters<'a>,
+    /// Textures of the render graph drawing the frame, if any
+    pub resources: Option<&'a Resources>,
+}
+
+/// Interface of everything that can be drawn, into a window or a framebuffer.
+///
+/// # Example
+/// ```no_run
+/// # fn example(display: &glium::Display, frame: &mut glium::Frame, context: &rt::RenderContext) -> rt::Result<()> {
+/// use rt::{Drawable, RenderTarget, Transform};
+/// use rt::shapes::mesh::MeshBuilder;
+/// use rt::shapes::sphere::SphereBuilder;
+/// let objects: Vec<(Box<dyn Drawable>, Transform)> = vec![
+///     (Box::new(SphereBuilder::new().build(display)?), Transform::default()),
+///     (Box::new(rt::shapes::star::Star::new(display)?), Transform { scale: 0.3, ..Default::default() }),
+/// ];
+///
+/// let mut target = RenderTarget::from(frame);
+/// for (object, transform) in &objects {
+///     object.draw(&mut target, context, transform)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait Drawable {
+    fn draw(&self, target: &mut RenderTarget, context: &RenderContext, transform: &Transform) -> Result<()>;
+}
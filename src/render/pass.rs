@@ -0,0 +1,125 @@
+use glium::DrawParameters;
+
+use crate::render::{BlendMode, Drawable, Pass};
+
+/// Where a pass draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassTarget {
+    /// The window
+    Screen,
+    /// A color texture of the graph, with one of its depth textures if the pass tests depth
+    Color { color: &'static str, depth: Option<&'static str> },
+    /// Only a depth texture of the graph, such as a shadow map
+    Depth(&'static str),
+}
+
+/// One step of the frame: which objects it draws, where and how.
+pub struct RenderPass {
+    pub(super) name: &'static str,
+    pub(super) kind: Pass,
+    pub(super) target: PassTarget,
+    pub(super) clear_color: Option<(f32, f32, f32, f32)>,
+    pub(super) clear_depth: Option<f32>,
+    pub(super) params: DrawParameters<'static>,
+    pub(super) reads: Vec<&'static str>,
+    /// Drawn by the pass itself before the queued objects, such as a full-screen effect
+    pub(super) effect: Option<Box<dyn Drawable>>,
+}
+
+impl RenderPass {
+    /// Creates a pass drawing the objects queued for `kind` on the screen, without clearing it.
+    ///
+    /// Transparent passes test the depth without writing it and blend with `BlendMode::Alpha`,
+    /// so the objects behind them stay visible. The other passes use the default draw parameters.
+    ///
+    /// # Example
+    /// ```
+    /// use rt::Pass;
+    /// use rt::render::{PassTarget, RenderPass};
+    /// let shadow = RenderPass::new("shadow", Pass::Shadow)
+    ///  .target(PassTarget::Depth("shadow_map"))
+    ///  .clear_depth(1.0);
+    /// ```
+    pub fn new(name: &'static str, kind: Pass) -> Self {
+        RenderPass {
+            name,
+            kind,
+            target: PassTarget::Screen,
+            clear_color: None,
+            clear_depth: None,
+            params: match kind {
+                Pass::Transparent => DrawParameters {
+                    depth: glium::Depth {
+                        test: glium::DepthTest::IfLess,
+                        write: false,
+                        ..Default::default()
+                    },
+                    blend: BlendMode::Alpha.blend(),
+                    ..Default::default()
+                },
+                _ => DrawParameters::default(),
+            },
+            reads: Vec::new(),
+            effect: None,
+        }
+    }
+
+    pub fn target(mut self, target: PassTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Clears the color of the target before drawing.
+    pub fn clear_color(mut self, color: (f32, f32, f32, f32)) -> Self {
+        self.clear_color = Some(color);
+        self
+    }
+
+    /// Clears the depth of the target before drawing.
+    pub fn clear_depth(mut self, depth: f32) -> Self {
+        self.clear_depth = Some(depth);
+        self
+    }
+
+    pub fn params(mut self, params: DrawParameters<'static>) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Blends the objects of the pass that don't choose a blend mode of their own.
+    pub fn blend(mut self, mode: BlendMode) -> Self {
+        self.params.blend = mode.blend();
+        self
+    }
+
+    /// Declares a texture of the graph sampled by the objects of this pass,
+    /// so the passes drawing into it run first.
+    pub fn reads(mut self, texture: &'static str) -> Self {
+        self.reads.push(texture);
+        self
+    }
+
+    /// Gives the pass something to draw on its own, whatever is queued.
+    /// Post-processing passes draw their effect this way.
+    pub fn effect(mut self, effect: impl Drawable + 'static) -> Self {
+        self.effect = Some(Box::new(effect));
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn kind(&self) -> Pass {
+        self.kind
+    }
+
+    /// Textures of the graph this pass draws into.
+    pub(super) fn writes(&self) -> Vec<&'static str> {
+        match self.target {
+            PassTarget::Screen => Vec::new(),
+            PassTarget::Color { color, depth } => std::iter::once(color).chain(depth).collect(),
+            PassTarget::Depth(depth) => vec![depth],
+        }
+    }
+}
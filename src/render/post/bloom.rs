@@ -0,0 +1,61 @@
+use glium::texture::UncompressedFloatFormat;
+use glium::Display;
+
+use crate::error::Result;
+use crate::render::post::FullscreenEffect;
+use crate::render::{Pass, PassTarget, RenderGraph, RenderPass};
+use crate::shader;
+
+/// Texture holding the bloom once every level is added up, at half the size of the window.
+pub const BLOOM_TEXTURE: &str = "bloom_up_1";
+
+/// Textures of the image halved again and again, each half the size of the previous one.
+const DOWN: [&str; 4] = ["bloom_down_1", "bloom_down_2", "bloom_down_3", "bloom_down_4"];
+/// Textures of the blurred levels added up, as big as the level of `DOWN` at the same index.
+const UP: [&str; 3] = [BLOOM_TEXTURE, "bloom_up_2", "bloom_up_3"];
+
+/// Adds the passes of a dual filter bloom of `source` to the graph.
+///
+/// What is brighter than `threshold` is kept while halving the image down the levels,
+/// then each level is blurred up and added to the level above, ending in `BLOOM_TEXTURE`.
+pub fn add_bloom(graph: RenderGraph, display: &Display, source: &'static str, threshold: f32) -> Result<RenderGraph> {
+    let format = UncompressedFloatFormat::F16F16F16F16;
+    let mut graph = DOWN.iter()
+        .enumerate()
+        .fold(graph, |graph, (level, &texture)| graph.scaled_color_texture(texture, format, 2 << level));
+    graph = UP.iter()
+        .enumerate()
+        .fold(graph, |graph, (level, &texture)| graph.scaled_color_texture(texture, format, 2 << level));
+
+    for (level, &texture) in DOWN.iter().enumerate() {
+        let (input, effect) = if level == 0 {
+            let effect = FullscreenEffect::new(display, shader!("bloom_down.frag"), &[("PREFILTER", "1")], &[("source", source)])?
+                .with("threshold", threshold);
+            (source, effect)
+        } else {
+            let input = DOWN[level - 1];
+            (input, FullscreenEffect::new(display, shader!("bloom_down.frag"), &[], &[("source", input)])?)
+        };
+        graph = graph.pass(
+            RenderPass::new(texture, Pass::PostProcess)
+                .target(PassTarget::Color { color: texture, depth: None })
+                .reads(input)
+                .effect(effect),
+        );
+    }
+
+    // The smallest level is blurred up first, each level adding the one below it
+    for (level, &texture) in UP.iter().enumerate().rev() {
+        let below = UP.get(level + 1).copied().unwrap_or(DOWN[level + 1]);
+        let effect = FullscreenEffect::new(display, shader!("bloom_up.frag"), &[], &[("source", below), ("base", DOWN[level])])?;
+        graph = graph.pass(
+            RenderPass::new(texture, Pass::PostProcess)
+                .target(PassTarget::Color { color: texture, depth: None })
+                .reads(below)
+                .reads(DOWN[level])
+                .effect(effect),
+        );
+    }
+
+    Ok(graph)
+}
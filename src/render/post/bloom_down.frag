@@ -0,0 +1,34 @@
+#version 330
+
+// Halves the image, averaging the center with the four diagonal texels.
+// PREFILTER keeps only what is brighter than the threshold, for the first level
+
+uniform sampler2D source;
+uniform vec2 texel_size;
+#ifdef PREFILTER
+uniform float threshold;
+#endif
+
+in vec2 v_tex_coords;
+out vec4 fragColor;
+
+void main() {
+    vec2 offset = texel_size;
+    vec3 color = texture(source, v_tex_coords).rgb * 4.0;
+    color += texture(source, v_tex_coords + vec2(-offset.x, -offset.y)).rgb;
+    color += texture(source, v_tex_coords + vec2(offset.x, -offset.y)).rgb;
+    color += texture(source, v_tex_coords + vec2(-offset.x, offset.y)).rgb;
+    color += texture(source, v_tex_coords + vec2(offset.x, offset.y)).rgb;
+    color /= 8.0;
+
+#ifdef PREFILTER
+    // Soft cut, so colors fade in rather than pop when crossing the threshold
+    float brightness = max(color.r, max(color.g, color.b));
+    float knee = threshold * 0.5;
+    float soft = clamp(brightness - threshold + knee, 0.0, 2.0 * knee);
+    soft = soft * soft / (4.0 * knee + 0.0001);
+    color *= max(soft, brightness - threshold) / max(brightness, 0.0001);
+#endif
+
+    fragColor = vec4(color, 1.0);
+}
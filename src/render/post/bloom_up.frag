@@ -0,0 +1,24 @@
+#version 330
+
+// Doubles the image with a tent filter over eight texels around the center,
+// adding the level of the same size
+
+uniform sampler2D source;
+uniform sampler2D base;
+uniform vec2 texel_size;
+
+in vec2 v_tex_coords;
+out vec4 fragColor;
+
+void main() {
+    vec2 offset = texel_size;
+    vec3 color = texture(source, v_tex_coords + vec2(-offset.x * 2.0, 0.0)).rgb;
+    color += texture(source, v_tex_coords + vec2(offset.x * 2.0, 0.0)).rgb;
+    color += texture(source, v_tex_coords + vec2(0.0, -offset.y * 2.0)).rgb;
+    color += texture(source, v_tex_coords + vec2(0.0, offset.y * 2.0)).rgb;
+    color += texture(source, v_tex_coords + vec2(-offset.x, -offset.y)).rgb * 2.0;
+    color += texture(source, v_tex_coords + vec2(offset.x, -offset.y)).rgb * 2.0;
+    color += texture(source, v_tex_coords + vec2(-offset.x, offset.y)).rgb * 2.0;
+    color += texture(source, v_tex_coords + vec2(offset.x, offset.y)).rgb * 2.0;
+    fragColor = vec4(color / 12.0 + texture(base, v_tex_coords).rgb, 1.0);
+}
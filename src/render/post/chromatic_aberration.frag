@@ -0,0 +1,24 @@
+#version 330
+
+// Moves the red outwards and the blue inwards, more towards the corners
+
+uniform sampler2D source;
+uniform vec2 texel_size;
+uniform int enabled;
+uniform float strength;
+
+in vec2 v_tex_coords;
+out vec4 fragColor;
+
+void main() {
+    if (enabled == 0) {
+        fragColor = vec4(texture(source, v_tex_coords).rgb, 1.0);
+        return;
+    }
+    // 1 at the corners
+    vec2 offset = (v_tex_coords - 0.5) * 2.0 * strength * texel_size;
+    float red = texture(source, v_tex_coords + offset).r;
+    float green = texture(source, v_tex_coords).g;
+    float blue = texture(source, v_tex_coords - offset).b;
+    fragColor = vec4(red, green, blue, 1.0);
+}
@@ -0,0 +1,34 @@
+#version 330
+
+// Looks the color up in a table of `lut_size` squares side by side, one for each blue,
+// red going right and green going up in each square
+
+uniform sampler2D source;
+uniform sampler2D lut;
+uniform int enabled;
+uniform float lut_size;
+uniform float strength;
+
+in vec2 v_tex_coords;
+out vec4 fragColor;
+
+vec3 look_up(vec3 color) {
+    float last = lut_size - 1.0;
+    float blue = color.b * last;
+    float first_square = floor(blue);
+    float second_square = min(first_square + 1.0, last);
+
+    // Centers of the texels of the first and last red and green
+    vec2 in_square = (color.rg * last + 0.5) / vec2(lut_size * lut_size, lut_size);
+    vec3 first = texture(lut, in_square + vec2(first_square / lut_size, 0.0)).rgb;
+    vec3 second = texture(lut, in_square + vec2(second_square / lut_size, 0.0)).rgb;
+    return mix(first, second, blue - first_square);
+}
+
+void main() {
+    vec3 color = texture(source, v_tex_coords).rgb;
+    if (enabled != 0) {
+        color = mix(color, look_up(clamp(color, 0.0, 1.0)), strength);
+    }
+    fragColor = vec4(color, 1.0);
+}
@@ -0,0 +1,25 @@
+#version 330
+
+// Adds noise changing every frame, stronger in the mid tones
+
+uniform sampler2D source;
+uniform int enabled;
+uniform float strength;
+uniform float time;
+
+in vec2 v_tex_coords;
+out vec4 fragColor;
+
+float hash(vec2 point) {
+    return fract(sin(dot(point, vec2(12.9898, 78.233))) * 43758.5453);
+}
+
+void main() {
+    vec3 color = texture(source, v_tex_coords).rgb;
+    if (enabled != 0) {
+        float noise = hash(gl_FragCoord.xy + fract(time) * 100.0) - 0.5;
+        float luma = dot(color, vec3(0.2126, 0.7152, 0.0722));
+        color += noise * strength * (1.0 - abs(luma * 2.0 - 1.0) * 0.5);
+    }
+    fragColor = vec4(clamp(color, 0.0, 1.0), 1.0);
+}
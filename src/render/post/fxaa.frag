@@ -0,0 +1,61 @@
+#version 330
+
+// Fast approximate anti-aliasing: blurs along the edges found from the contrast of the luma.
+// `edge_threshold` is the smallest contrast taken as an edge, `subpixel` how much thin
+// details are smoothed
+
+uniform sampler2D source;
+uniform vec2 texel_size;
+uniform int enabled;
+uniform float edge_threshold;
+uniform float subpixel;
+
+in vec2 v_tex_coords;
+out vec4 fragColor;
+
+float luma(vec3 color) {
+    return dot(color, vec3(0.299, 0.587, 0.114));
+}
+
+void main() {
+    vec3 center = texture(source, v_tex_coords).rgb;
+    if (enabled == 0) {
+        fragColor = vec4(center, 1.0);
+        return;
+    }
+
+    float luma_center = luma(center);
+    float luma_nw = luma(texture(source, v_tex_coords + vec2(-1.0, 1.0) * texel_size).rgb);
+    float luma_ne = luma(texture(source, v_tex_coords + vec2(1.0, 1.0) * texel_size).rgb);
+    float luma_sw = luma(texture(source, v_tex_coords + vec2(-1.0, -1.0) * texel_size).rgb);
+    float luma_se = luma(texture(source, v_tex_coords + vec2(1.0, -1.0) * texel_size).rgb);
+
+    float luma_min = min(luma_center, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    float luma_max = max(luma_center, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+    if (luma_max - luma_min < max(edge_threshold * luma_max, 0.0312)) {
+        fragColor = vec4(center, 1.0);
+        return;
+    }
+
+    // Direction along the edge, across the gradient of the luma
+    vec2 direction = vec2(
+        -((luma_nw + luma_ne) - (luma_sw + luma_se)),
+        (luma_nw + luma_sw) - (luma_ne + luma_se)
+    );
+    float reduce = max((luma_nw + luma_ne + luma_sw + luma_se) * 0.25 * (1.0 - subpixel) * 0.125, 1.0 / 128.0);
+    float scale = 1.0 / (min(abs(direction.x), abs(direction.y)) + reduce);
+    direction = clamp(direction * scale, vec2(-8.0), vec2(8.0)) * texel_size;
+
+    vec3 near = 0.5 * (
+        texture(source, v_tex_coords + direction * (1.0 / 3.0 - 0.5)).rgb +
+        texture(source, v_tex_coords + direction * (2.0 / 3.0 - 0.5)).rgb
+    );
+    vec3 far = near * 0.5 + 0.25 * (
+        texture(source, v_tex_coords - direction * 0.5).rgb +
+        texture(source, v_tex_coords + direction * 0.5).rgb
+    );
+
+    // The far samples crossed the edge if they leave the range of the neighbours
+    float luma_far = luma(far);
+    fragColor = vec4(luma_far < luma_min || luma_far > luma_max ? near : far, 1.0);
+}
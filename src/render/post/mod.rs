@@ -0,0 +1,176 @@
+//! Full-screen effects drawn by the post-processing passes of a render graph.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glium::index::{NoIndices, PrimitiveType};
+use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, SamplerBehavior, SamplerWrapFunction, UniformValue, Uniforms};
+use glium::texture::Texture2d;
+use glium::{Display, Surface, VertexBuffer};
+
+use crate::error::{Error, Result};
+use crate::render::{Drawable, RenderContext, RenderTarget};
+use crate::shader;
+use crate::shapes::programs::{ProgramCache, Shader, ShaderProgram};
+use crate::shapes::Transform;
+
+pub use self::bloom::{add_bloom, BLOOM_TEXTURE};
+pub use self::stack::{PostEffect, PostStack, PostStackBuilder};
+pub use self::tone_mapping::{tone_mapping_pass, ToneMapping};
+
+mod bloom;
+mod stack;
+mod tone_mapping;
+
+#[derive(Debug, Clone, Copy)]
+struct QuadVertex {
+    position: [f32; 2],
+}
+
+implement_vertex!(QuadVertex, position);
+
+/// Value of a setting of an effect, passed to its shader as a uniform of the same name.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EffectValue {
+    Float(f32),
+    Int(i32),
+}
+
+impl From<f32> for EffectValue {
+    fn from(value: f32) -> Self {
+        EffectValue::Float(value)
+    }
+}
+
+impl From<i32> for EffectValue {
+    fn from(value: i32) -> Self {
+        EffectValue::Int(value)
+    }
+}
+
+/// Settings of an effect, shared with whoever changes them while the graph owns the effect.
+#[derive(Debug, Default)]
+pub struct EffectSettings {
+    values: RefCell<Vec<(&'static str, EffectValue)>>,
+}
+
+impl EffectSettings {
+    pub fn set(&self, name: &'static str, value: impl Into<EffectValue>) {
+        let value = value.into();
+        let mut values = self.values.borrow_mut();
+        match values.iter_mut().find(|(setting, _)| *setting == name) {
+            Some((_, old)) => *old = value,
+            None => values.push((name, value)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<EffectValue> {
+        self.values.borrow().iter().find(|(setting, _)| *setting == name).map(|(_, value)| *value)
+    }
+}
+
+/// Uniforms of an effect, known only when it is drawn.
+struct EffectUniforms<'a> {
+    values: Vec<(&'a str, UniformValue<'a>)>,
+}
+
+impl Uniforms for EffectUniforms<'_> {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut visit: F) {
+        for &(name, value) in &self.values {
+            visit(name, value);
+        }
+    }
+}
+
+/// Fragment shader drawn over the whole target, reading textures of the render graph.
+///
+/// Each input binds a texture of the graph to a sampler of the shader.
+/// The shader also gets `texel_size`, the size of a texel of its first input,
+/// `time` from the context and a uniform for each of its settings.
+pub struct FullscreenEffect {
+    quad: VertexBuffer<QuadVertex>,
+    program: Rc<ShaderProgram>,
+    /// Samplers of the shader with the textures they read
+    inputs: Vec<(&'static str, &'static str)>,
+    /// Samplers of the shader with textures of the effect itself, such as a lookup table
+    textures: Vec<(&'static str, Texture2d)>,
+    settings: Rc<EffectSettings>,
+}
+
+impl FullscreenEffect {
+    pub fn new(
+        display: &Display,
+        fragment: Shader,
+        defines: &[(&'static str, &str)],
+        inputs: &[(&'static str, &'static str)],
+    ) -> Result<Self> {
+        let corners = [[-1.0, -1.0], [1.0, -1.0], [-1.0, 1.0], [1.0, 1.0]].map(|position| QuadVertex { position });
+        Ok(FullscreenEffect {
+            quad: VertexBuffer::new(display, &corners)?,
+            program: ProgramCache::for_display(display).get(shader!("fullscreen.vert"), fragment, defines)?,
+            inputs: inputs.to_vec(),
+            textures: Vec::new(),
+            settings: Rc::new(EffectSettings::default()),
+        })
+    }
+
+    /// Sets the starting value of a setting.
+    pub fn with(self, name: &'static str, value: impl Into<EffectValue>) -> Self {
+        self.settings.set(name, value);
+        self
+    }
+
+    /// Binds a texture owned by the effect to a sampler of the shader.
+    pub fn with_texture(mut self, sampler: &'static str, texture: Texture2d) -> Self {
+        self.textures.push((sampler, texture));
+        self
+    }
+
+    /// Settings of the effect, to change them after the effect is given to a pass.
+    pub fn settings(&self) -> Rc<EffectSettings> {
+        Rc::clone(&self.settings)
+    }
+}
+
+impl Drawable for FullscreenEffect {
+    fn draw(&self, target: &mut RenderTarget, context: &RenderContext, _transform: &Transform) -> Result<()> {
+        let resources = context.resources
+            .ok_or_else(|| Error::RenderGraph("full-screen effects are only drawn by a render graph".to_string()))?;
+
+        let sampler = SamplerBehavior {
+            wrap_function: (SamplerWrapFunction::Clamp, SamplerWrapFunction::Clamp, SamplerWrapFunction::Clamp),
+            minify_filter: MinifySamplerFilter::Linear,
+            magnify_filter: MagnifySamplerFilter::Linear,
+            ..Default::default()
+        };
+
+        let mut values = vec![("time", UniformValue::Float(context.time))];
+        for (index, &(sampler_name, texture)) in self.inputs.iter().enumerate() {
+            let texture = resources.color(texture)
+                .ok_or_else(|| Error::RenderGraph(format!("the effect reads the unknown texture {texture}")))?;
+            if index == 0 {
+                values.push(("texel_size", UniformValue::Vec2([1.0 / texture.width() as f32, 1.0 / texture.height() as f32])));
+            }
+            values.push((sampler_name, UniformValue::Texture2d(texture, Some(sampler))));
+        }
+        values.extend(self.textures.iter().map(|(name, texture)| (*name, UniformValue::Texture2d(texture, Some(sampler)))));
+
+        let settings = self.settings.values.borrow();
+        values.extend(settings.iter().map(|&(name, value)| {
+            let value = match value {
+                EffectValue::Float(value) => UniformValue::Float(value),
+                EffectValue::Int(value) => UniformValue::SignedInt(value),
+            };
+            (name, value)
+        }));
+
+        target.draw(
+            &self.quad,
+            NoIndices(PrimitiveType::TriangleStrip),
+            &self.program.get(),
+            &EffectUniforms { values },
+            &context.params,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,320 @@
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use glium::texture::{RawImage2d, Texture2d, UncompressedFloatFormat};
+use glium::Display;
+
+use crate::error::{Error, Result};
+use crate::render::post::{EffectSettings, EffectValue, FullscreenEffect};
+use crate::render::{Pass, PassTarget, RenderGraph, RenderPass};
+use crate::scene::SceneFile;
+use crate::shader;
+use crate::shapes::programs::Shader;
+
+/// Textures between the effects of the stack, the last effect drawing on the screen.
+const STAGES: [&str; 4] = ["post_1", "post_2", "post_3", "post_4"];
+
+/// Size of the side of the color grading lookup table made when no image is given.
+const LUT_SIZE: u32 = 16;
+
+/// Full-screen effect of the post-processing stack, drawn after the tone mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostEffect {
+    /// Smooths the jagged edges of the shapes
+    Fxaa,
+    /// Darkens the corners of the screen
+    Vignette,
+    /// Noise changing every frame, like on film
+    FilmGrain,
+    /// Splits the red and blue towards the corners, like a cheap lens
+    ChromaticAberration,
+    /// Changes the colors through a lookup table
+    ColorGrading,
+}
+
+impl PostEffect {
+    /// Every effect, in the order they are drawn unless told otherwise.
+    pub const ALL: [PostEffect; 5] = [
+        PostEffect::Fxaa,
+        PostEffect::ChromaticAberration,
+        PostEffect::ColorGrading,
+        PostEffect::Vignette,
+        PostEffect::FilmGrain,
+    ];
+
+    /// Name of the effect in scene files, also the name of its pass.
+    pub fn name(self) -> &'static str {
+        match self {
+            PostEffect::Fxaa => "fxaa",
+            PostEffect::Vignette => "vignette",
+            PostEffect::FilmGrain => "film_grain",
+            PostEffect::ChromaticAberration => "chromatic_aberration",
+            PostEffect::ColorGrading => "color_grading",
+        }
+    }
+
+    /// Settings of the effect with their starting values.
+    pub fn defaults(self) -> &'static [(&'static str, f32)] {
+        match self {
+            PostEffect::Fxaa => &[("edge_threshold", 0.125), ("subpixel", 0.75)],
+            PostEffect::Vignette => &[("strength", 0.35), ("radius", 0.75)],
+            PostEffect::FilmGrain => &[("strength", 0.04)],
+            // In texels at the corners of the screen
+            PostEffect::ChromaticAberration => &[("strength", 2.0)],
+            PostEffect::ColorGrading => &[("strength", 1.0)],
+        }
+    }
+
+    fn shader(self) -> Shader {
+        match self {
+            PostEffect::Fxaa => shader!("fxaa.frag"),
+            PostEffect::Vignette => shader!("vignette.frag"),
+            PostEffect::FilmGrain => shader!("film_grain.frag"),
+            PostEffect::ChromaticAberration => shader!("chromatic_aberration.frag"),
+            PostEffect::ColorGrading => shader!("color_grading.frag"),
+        }
+    }
+
+    /// Static name of a setting, if the effect has it.
+    fn setting(self, name: &str) -> Option<&'static str> {
+        self.defaults().iter().map(|(setting, _)| *setting).find(|setting| *setting == name)
+    }
+}
+
+impl FromStr for PostEffect {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        PostEffect::ALL.into_iter()
+            .find(|effect| effect.name() == name)
+            .ok_or_else(|| Error::Scene(format!("unknown post-processing effect {name}")))
+    }
+}
+
+/// Effects of the stack with their settings, to turn them on and off and change them while drawing.
+///
+/// Every effect keeps its pass in the graph; one turned off copies its image unchanged.
+pub struct PostStack {
+    effects: Vec<(PostEffect, Rc<EffectSettings>)>,
+}
+
+impl PostStack {
+    /// Effects in the order they are drawn.
+    pub fn order(&self) -> impl Iterator<Item = PostEffect> + '_ {
+        self.effects.iter().map(|(effect, _)| *effect)
+    }
+
+    /// Whether an effect of the stack is on, never for an effect the stack doesn't have.
+    pub fn is_enabled(&self, effect: PostEffect) -> bool {
+        self.settings(effect).is_ok_and(|settings| settings.get("enabled") != Some(EffectValue::Int(0)))
+    }
+
+    pub fn set_enabled(&self, effect: PostEffect, enabled: bool) -> Result<()> {
+        self.settings(effect)?.set("enabled", enabled as i32);
+        Ok(())
+    }
+
+    /// Turns an effect on if it is off and off if it is on, giving back whether it is now on.
+    pub fn toggle(&self, effect: PostEffect) -> Result<bool> {
+        let enabled = !self.is_enabled(effect);
+        self.set_enabled(effect, enabled)?;
+        Ok(enabled)
+    }
+
+    /// Changes a setting listed in the defaults of the effect.
+    pub fn set(&self, effect: PostEffect, setting: &str, value: f32) -> Result<()> {
+        let setting = effect.setting(setting)
+            .ok_or_else(|| Error::RenderGraph(format!("the effect {} has no setting {setting}", effect.name())))?;
+        self.settings(effect)?.set(setting, value);
+        Ok(())
+    }
+
+    fn settings(&self, effect: PostEffect) -> Result<&EffectSettings> {
+        self.effects.iter()
+            .find(|(e, _)| *e == effect)
+            .map(|(_, settings)| settings.as_ref())
+            .ok_or_else(|| Error::RenderGraph(format!("the effect {} is not in the stack", effect.name())))
+    }
+}
+
+impl Default for PostStackBuilder {
+    fn default() -> Self {
+        PostStackBuilder::new()
+    }
+}
+
+/// Helps to add the post-processing stack to a render graph.
+pub struct PostStackBuilder {
+    enabled: Vec<PostEffect>,
+    settings: Vec<(PostEffect, String, f32)>,
+    lut: Option<PathBuf>,
+}
+
+impl PostStackBuilder {
+    /// Creates a stack with every effect on, in the order of `PostEffect::ALL`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display, graph: rt::render::RenderGraph) -> rt::Result<()> {
+    /// use rt::render::post::{PostEffect, PostStackBuilder};
+    /// let (graph, stack) = PostStackBuilder::new()
+    ///  .enabled(&[PostEffect::Fxaa, PostEffect::Vignette])
+    ///  .setting(PostEffect::Vignette, "strength", 0.5)
+    ///  .build(graph, display, "ldr")?;
+    /// stack.toggle(PostEffect::FilmGrain)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new() -> Self {
+        PostStackBuilder {
+            enabled: PostEffect::ALL.to_vec(),
+            settings: Vec::new(),
+            lut: None,
+        }
+    }
+
+    /// Reads the stack from a scene file.
+    ///
+    /// `post` lists the effects turned on, in the order they are drawn,
+    /// `<effect>.<setting>` changes a setting and `color_grading.lut` is the lookup table image.
+    pub fn from_scene(scene: &SceneFile) -> Result<Self> {
+        let mut builder = PostStackBuilder::new();
+        if let Some(names) = scene.list("post") {
+            let effects = names.into_iter().map(str::parse).collect::<Result<Vec<PostEffect>>>()?;
+            builder = builder.enabled(&effects);
+        }
+        for effect in PostEffect::ALL {
+            for (setting, value) in scene.group(effect.name()) {
+                if effect == PostEffect::ColorGrading && setting == "lut" {
+                    builder = builder.lut(value);
+                    continue;
+                }
+                if effect.setting(setting).is_none() {
+                    return Err(Error::Scene(format!("the effect {} has no setting {setting}", effect.name())));
+                }
+                let value = value.parse()
+                    .map_err(|_| Error::Scene(format!("{}.{setting} can't be {value}", effect.name())))?;
+                builder = builder.setting(effect, setting, value);
+            }
+        }
+        Ok(builder)
+    }
+
+    /// Sets the effects turned on, in the order they are drawn.
+    /// The other effects are drawn after them, turned off.
+    pub fn enabled(mut self, effects: &[PostEffect]) -> Self {
+        self.enabled = effects.to_vec();
+        self
+    }
+
+    /// Sets the starting value of a setting of an effect.
+    pub fn setting(mut self, effect: PostEffect, setting: &str, value: f32) -> Self {
+        self.settings.push((effect, setting.to_string(), value));
+        self
+    }
+
+    /// Sets the image of the color grading lookup table: squares of the red and green
+    /// side by side, one for each blue, with green going down.
+    /// Without it, a slight warm and teal grade is made.
+    pub fn lut(mut self, path: impl Into<PathBuf>) -> Self {
+        self.lut = Some(path.into());
+        self
+    }
+
+    /// Adds a pass for each effect to the graph, the first one reading `source`
+    /// and the last one drawing on the screen.
+    pub fn build(self, graph: RenderGraph, display: &Display, source: &'static str) -> Result<(RenderGraph, PostStack)> {
+        let mut order = Vec::new();
+        for &effect in self.enabled.iter().chain(PostEffect::ALL.iter()) {
+            if !order.contains(&effect) {
+                order.push(effect);
+            }
+        }
+
+        let mut graph = STAGES.iter().fold(graph, |graph, &stage| graph.color_texture(stage, UncompressedFloatFormat::U8U8U8U8));
+        let mut effects = Vec::new();
+        let mut input = source;
+        for (index, &effect) in order.iter().enumerate() {
+            let mut fullscreen = FullscreenEffect::new(display, effect.shader(), &[], &[("source", input)])?
+                .with("enabled", self.enabled.contains(&effect) as i32);
+            for &(setting, value) in effect.defaults() {
+                fullscreen = fullscreen.with(setting, value);
+            }
+            if effect == PostEffect::ColorGrading {
+                let (lut, size) = self.load_lut(display)?;
+                fullscreen = fullscreen.with_texture("lut", lut).with("lut_size", size as f32);
+            }
+            effects.push((effect, fullscreen.settings()));
+
+            let target = match STAGES.get(index) {
+                Some(&stage) if index + 1 < order.len() => PassTarget::Color { color: stage, depth: None },
+                _ => PassTarget::Screen,
+            };
+            graph = graph.pass(
+                RenderPass::new(effect.name(), Pass::PostProcess)
+                    .target(target)
+                    .reads(input)
+                    .effect(fullscreen),
+            );
+            if let PassTarget::Color { color, .. } = target {
+                input = color;
+            }
+        }
+
+        let stack = PostStack { effects };
+        for (effect, setting, value) in &self.settings {
+            stack.set(*effect, setting, *value)?;
+        }
+        Ok((graph, stack))
+    }
+
+    /// Lookup table of the color grading with the size of its side.
+    fn load_lut(&self, display: &Display) -> Result<(Texture2d, u32)> {
+        let (pixels, size) = match &self.lut {
+            Some(path) => {
+                let image = image::open(path)
+                    .map_err(|e| Error::Texture(format!("Could not load the lookup table {}: {e}", path.display())))?
+                    .to_rgba8();
+                let (width, height) = image.dimensions();
+                if width != height * height {
+                    return Err(Error::Texture(format!(
+                        "The lookup table {} should be {} pixels wide, the square of its height",
+                        path.display(),
+                        height * height,
+                    )));
+                }
+                (image.into_raw(), height)
+            }
+            None => (default_lut(LUT_SIZE), LUT_SIZE),
+        };
+
+        // Not flipped, so the first row of the image, where green is 0, is at v = 0
+        let image = RawImage2d::from_raw_rgba(pixels, (size * size, size));
+        let texture = Texture2d::with_format(display, image, UncompressedFloatFormat::U8U8U8U8, glium::texture::MipmapsOption::NoMipmap)
+            .map_err(|e| Error::Texture(format!("Could not create the lookup table: {e}")))?;
+        Ok((texture, size))
+    }
+}
+
+/// Lookup table pulling the shadows towards teal and the highlights towards orange.
+fn default_lut(size: u32) -> Vec<u8> {
+    let step = 1.0 / (size - 1) as f32;
+    let mut pixels = Vec::with_capacity((size * size * size * 4) as usize);
+    for green in 0..size {
+        for blue in 0..size {
+            for red in 0..size {
+                let color = [red as f32 * step, green as f32 * step, blue as f32 * step];
+                let luma = 0.2126 * color[0] + 0.7152 * color[1] + 0.0722 * color[2];
+                let shadow = [-0.03, 0.01, 0.04].map(|tint| tint * (1.0 - luma));
+                let highlight = [0.05, 0.02, -0.03].map(|tint| tint * luma);
+                for channel in 0..3 {
+                    let graded = (color[channel] + shadow[channel] + highlight[channel]).clamp(0.0, 1.0);
+                    pixels.push((graded * 255.0).round() as u8);
+                }
+                pixels.push(255);
+            }
+        }
+    }
+    pixels
+}
@@ -0,0 +1,40 @@
+#version 330
+
+// Brings the HDR scene back between 0 and 1.
+// HAS_BLOOM adds the bloom to the scene first
+
+uniform sampler2D scene;
+#ifdef HAS_BLOOM
+uniform sampler2D bloom;
+uniform float bloom_strength;
+#endif
+uniform float exposure;
+// 0 for Reinhard, 1 for ACES
+uniform int operator;
+
+in vec2 v_tex_coords;
+out vec4 fragColor;
+
+vec3 reinhard(vec3 color) {
+    return color / (color + 1.0);
+}
+
+// Fit of the ACES filmic curve by Krzysztof Narkowicz
+vec3 aces(vec3 color) {
+    return clamp((color * (2.51 * color + 0.03)) / (color * (2.43 * color + 0.59) + 0.14), 0.0, 1.0);
+}
+
+void main() {
+    vec3 color = texture(scene, v_tex_coords).rgb;
+#ifdef HAS_BLOOM
+    color += texture(bloom, v_tex_coords).rgb * bloom_strength;
+#endif
+    color *= exposure;
+
+    if (operator == 1) {
+        color = aces(color);
+    } else {
+        color = reinhard(color);
+    }
+    fragColor = vec4(color, 1.0);
+}
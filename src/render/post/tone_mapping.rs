@@ -0,0 +1,64 @@
+use std::rc::Rc;
+
+use glium::Display;
+
+use crate::error::Result;
+use crate::render::post::{EffectSettings, FullscreenEffect};
+use crate::render::{Pass, RenderPass};
+use crate::shader;
+
+/// Curve bringing the colors of the HDR image back between 0 and 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToneMapping {
+    /// Keeps the colors of the scene, compressing only the brightest ones
+    Reinhard,
+    /// Filmic curve with more contrast and saturation
+    Aces,
+}
+
+impl ToneMapping {
+    /// The other curve, to switch between them.
+    pub fn next(self) -> Self {
+        match self {
+            ToneMapping::Reinhard => ToneMapping::Aces,
+            ToneMapping::Aces => ToneMapping::Reinhard,
+        }
+    }
+
+    /// Number the shader knows the curve by.
+    pub fn id(self) -> i32 {
+        match self {
+            ToneMapping::Reinhard => 0,
+            ToneMapping::Aces => 1,
+        }
+    }
+}
+
+/// Creates the pass drawing the HDR `scene`, with the `bloom` added to it if any.
+/// It draws on the screen unless given another target.
+///
+/// The settings are `exposure`, which the colors are multiplied by, `bloom_strength`
+/// and `operator`, the id of the `ToneMapping` curve.
+pub fn tone_mapping_pass(
+    display: &Display,
+    scene: &'static str,
+    bloom: Option<&'static str>,
+) -> Result<(RenderPass, Rc<EffectSettings>)> {
+    let mut inputs = vec![("scene", scene)];
+    let mut defines = Vec::new();
+    if let Some(bloom) = bloom {
+        inputs.push(("bloom", bloom));
+        defines.push(("HAS_BLOOM", "1"));
+    }
+
+    let effect = FullscreenEffect::new(display, shader!("tone_mapping.frag"), &defines, &inputs)?
+        .with("exposure", 1.0)
+        .with("bloom_strength", 0.3)
+        .with("operator", ToneMapping::Aces.id());
+    let settings = effect.settings();
+
+    let pass = inputs.iter()
+        .fold(RenderPass::new("tone_mapping", Pass::PostProcess), |pass, &(_, texture)| pass.reads(texture))
+        .effect(effect);
+    Ok((pass, settings))
+}
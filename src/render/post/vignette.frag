@@ -0,0 +1,20 @@
+#version 330
+
+// Darkens the screen from `radius`, the distance from the center to a corner being 1
+
+uniform sampler2D source;
+uniform int enabled;
+uniform float strength;
+uniform float radius;
+
+in vec2 v_tex_coords;
+out vec4 fragColor;
+
+void main() {
+    vec3 color = texture(source, v_tex_coords).rgb;
+    if (enabled != 0) {
+        float distance = length(v_tex_coords - 0.5) / length(vec2(0.5));
+        color *= 1.0 - strength * smoothstep(radius, 1.0 + radius * 0.25, distance);
+    }
+    fragColor = vec4(color, 1.0);
+}
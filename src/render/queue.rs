@@ -0,0 +1,53 @@
+use crate::render::{Drawable, Pass};
+use crate::shapes::matrices::{multiply, transform_point};
+use crate::shapes::Transform;
+
+/// Object waiting to be drawn in a pass.
+pub struct QueuedObject<'a> {
+    pub name: &'a str,
+    pub object: &'a dyn Drawable,
+    pub transform: Transform,
+}
+
+/// Objects of a frame, grouped by the pass they are drawn in.
+#[derive(Default)]
+pub struct RenderQueue<'a> {
+    objects: Vec<(Pass, QueuedObject<'a>)>,
+}
+
+impl<'a> RenderQueue<'a> {
+    pub fn new() -> Self {
+        RenderQueue::default()
+    }
+
+    /// Queues an object for a pass. An object may be queued in several passes,
+    /// such as the opaque and the shadow passes.
+    pub fn push(&mut self, pass: Pass, name: &'a str, object: &'a dyn Drawable, transform: Transform) {
+        self.objects.push((pass, QueuedObject { name, object, transform }));
+    }
+
+    /// Objects of a pass, in the order they were queued.
+    pub fn pass(&self, pass: Pass) -> impl Iterator<Item = &QueuedObject<'a>> {
+        self.objects.iter()
+            .filter(move |(queued_pass, _)| *queued_pass == pass)
+            .map(|(_, object)| object)
+    }
+
+    /// Objects of a pass from the farthest to the nearest to the camera,
+    /// so each transparent object blends over the ones behind it.
+    pub fn back_to_front(&self, pass: Pass) -> Vec<&QueuedObject<'a>> {
+        let mut objects: Vec<(f32, &QueuedObject<'a>)> = self.pass(pass)
+            .map(|object| (object.depth(), object))
+            .collect();
+        objects.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        objects.into_iter().map(|(_, object)| object).collect()
+    }
+}
+
+impl QueuedObject<'_> {
+    /// Distance along the view direction from the camera to the center of the object.
+    fn depth(&self) -> f32 {
+        let view_model = multiply(&self.transform.get_view(), &self.transform.get_model());
+        transform_point(&view_model, [0.0; 3])[2]
+    }
+}
@@ -0,0 +1,102 @@
+use glium::framebuffer::{MultiOutputFrameBuffer, SimpleFrameBuffer};
+use glium::uniforms::{MagnifySamplerFilter, Uniforms};
+use glium::{index, vertex, BlitMask, BlitTarget, DrawError, DrawParameters, Frame, Program, Rect, Surface};
+
+/// Surface a `Drawable` draws into: the window or one of the framebuffers.
+///
+/// `Surface` has generic methods, so it can't be a trait object;
+/// this enum stands for any of them behind a single type.
+pub enum RenderTarget<'a, 'b> {
+    Frame(&'a mut Frame),
+    Framebuffer(&'a mut SimpleFrameBuffer<'b>),
+    MultiOutput(&'a mut MultiOutputFrameBuffer<'b>),
+}
+
+impl<'a> From<&'a mut Frame> for RenderTarget<'a, '_> {
+    fn from(frame: &'a mut Frame) -> Self {
+        RenderTarget::Frame(frame)
+    }
+}
+
+impl<'a, 'b> From<&'a mut SimpleFrameBuffer<'b>> for RenderTarget<'a, 'b> {
+    fn from(framebuffer: &'a mut SimpleFrameBuffer<'b>) -> Self {
+        RenderTarget::Framebuffer(framebuffer)
+    }
+}
+
+impl<'a, 'b> From<&'a mut MultiOutputFrameBuffer<'b>> for RenderTarget<'a, 'b> {
+    fn from(framebuffer: &'a mut MultiOutputFrameBuffer<'b>) -> Self {
+        RenderTarget::MultiOutput(framebuffer)
+    }
+}
+
+/// Calls the same method on whichever surface the target holds.
+macro_rules! dispatch {
+    ($target: expr, $surface: ident => $call: expr) => {
+        match $target {
+            RenderTarget::Frame($surface) => $call,
+            RenderTarget::Framebuffer($surface) => $call,
+            RenderTarget::MultiOutput($surface) => $call,
+        }
+    };
+}
+
+impl Surface for RenderTarget<'_, '_> {
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool, depth: Option<f32>, stencil: Option<i32>) {
+        dispatch!(self, surface => surface.clear(rect, color, color_srgb, depth, stencil))
+    }
+
+    fn get_dimensions(&self) -> (u32, u32) {
+        dispatch!(self, surface => surface.get_dimensions())
+    }
+
+    fn get_depth_buffer_bits(&self) -> Option<u16> {
+        dispatch!(self, surface => surface.get_depth_buffer_bits())
+    }
+
+    fn get_stencil_buffer_bits(&self) -> Option<u16> {
+        dispatch!(self, surface => surface.get_stencil_buffer_bits())
+    }
+
+    fn draw<'c, 'd, V, I, U>(&mut self, vertices: V, indices: I, program: &Program, uniforms: &U, params: &DrawParameters<'_>) -> Result<(), DrawError>
+    where
+        V: vertex::MultiVerticesSource<'d>,
+        I: Into<index::IndicesSource<'c>>,
+        U: Uniforms,
+    {
+        dispatch!(self, surface => surface.draw(vertices, indices, program, uniforms, params))
+    }
+
+    fn blit_buffers_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
+        dispatch!(self, surface => surface.blit_buffers_from_frame(source_rect, target_rect, filter, mask))
+    }
+
+    fn blit_buffers_from_simple_framebuffer(
+        &self,
+        source: &SimpleFrameBuffer<'_>,
+        source_rect: &Rect,
+        target_rect: &BlitTarget,
+        filter: MagnifySamplerFilter,
+        mask: BlitMask,
+    ) {
+        dispatch!(self, surface => surface.blit_buffers_from_simple_framebuffer(source, source_rect, target_rect, filter, mask))
+    }
+
+    fn blit_buffers_from_multioutput_framebuffer(
+        &self,
+        source: &MultiOutputFrameBuffer<'_>,
+        source_rect: &Rect,
+        target_rect: &BlitTarget,
+        filter: MagnifySamplerFilter,
+        mask: BlitMask,
+    ) {
+        dispatch!(self, surface => surface.blit_buffers_from_multioutput_framebuffer(source, source_rect, target_rect, filter, mask))
+    }
+
+    fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget, filter: MagnifySamplerFilter)
+    where
+        S: Surface,
+    {
+        dispatch!(self, surface => surface.blit_color(source_rect, target, target_rect, filter))
+    }
+}
@@ -0,0 +1,76 @@
+//! Scene files: plain `key = value` lines setting up the scene without recompiling.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// Settings read from a scene file, in the order they are written.
+///
+/// Each line is `key = value`, and lines starting with `#` are comments.
+/// Keys are grouped with dots, such as `vignette.strength`.
+///
+/// ```
+/// let scene = rt::SceneFile::parse("
+///     post = fxaa, vignette
+///     vignette.strength = 0.4
+/// ").unwrap();
+///
+/// assert_eq!(scene.list("post"), Some(vec!["fxaa", "vignette"]));
+/// assert_eq!(scene.value::<f32>("vignette.strength").unwrap(), Some(0.4));
+/// assert_eq!(scene.group("vignette").collect::<Vec<_>>(), [("strength", "0.4")]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SceneFile {
+    entries: Vec<(String, String)>,
+}
+
+impl SceneFile {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| Error::Scene(format!("line {} should be key = value: {line}", number + 1)))?;
+            entries.push((key.trim().to_string(), value.trim().to_string()));
+        }
+        Ok(SceneFile { entries })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .map_err(|e| Error::Scene(format!("Could not read {}: {e}", path.display())))?;
+        SceneFile::parse(&source)
+    }
+
+    /// Value of a key, the last one written if the key is repeated.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().rev().find(|(k, _)| k == key).map(|(_, value)| value.as_str())
+    }
+
+    /// Value of a key parsed as a number or any other type read from a string.
+    pub fn value<T: FromStr>(&self, key: &str) -> Result<Option<T>> {
+        self.get(key)
+            .map(|value| value.parse().map_err(|_| Error::Scene(format!("{key} can't be {value}"))))
+            .transpose()
+    }
+
+    /// Value of a key split at its commas, such as a list of effects.
+    pub fn list(&self, key: &str) -> Option<Vec<&str>> {
+        self.get(key).map(|value| value.split(',').map(str::trim).filter(|item| !item.is_empty()).collect())
+    }
+
+    /// Keys starting with `group.`, without that prefix, with their values.
+    pub fn group<'a>(&'a self, group: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.entries.iter().filter_map(move |(key, value)| {
+            key.strip_prefix(group)
+                .and_then(|key| key.strip_prefix('.'))
+                .map(|key| (key, value.as_str()))
+        })
+    }
+}
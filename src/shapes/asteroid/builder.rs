@@ -0,0 +1,164 @@
+#![allow(dead_code)]
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::shapes::mesh::{compute_normals, compute_tangents, Material, MeshBuilder};
+use crate::shapes::noise::Perlin;
+use crate::shapes::sphere::icosphere;
+use crate::shapes::vectors::{add, dot, scale};
+use crate::shapes::Vertex;
+
+/// Helps to create an asteroid.
+///
+/// The same seed always gives the same asteroid.
+pub struct AsteroidBuilder {
+    seed: u64,
+    radius: f32,
+    subdivisions: usize,
+    roughness: f32,
+    craters: usize,
+    color: [f32; 3],
+}
+
+impl AsteroidBuilder {
+    /// Creates a new asteroid builder.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::asteroid::AsteroidBuilder;
+    /// use rt::shapes::mesh::MeshBuilder;
+    /// let asteroid = AsteroidBuilder::new()
+    ///  .seed(42)
+    ///  .radius(0.5)
+    ///  .craters(8)
+    ///  .build(display)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new() -> Self {
+        AsteroidBuilder {
+            seed: 0,
+            radius: 1.0,
+            subdivisions: 4,
+            roughness: 0.2,
+            craters: 12,
+            color: [0.4, 0.38, 0.35],
+        }
+    }
+
+    /// Sets the seed of the noise and craters.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the radius of the sphere the asteroid is carved from.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets how many times the icosphere triangles are split in four.
+    pub fn subdivisions(mut self, subdivisions: usize) -> Self {
+        self.subdivisions = subdivisions;
+        self
+    }
+
+    /// Sets how far the noise moves the surface, relative to the radius.
+    pub fn roughness(mut self, roughness: f32) -> Self {
+        self.roughness = roughness;
+        self
+    }
+
+    /// Sets the number of craters.
+    pub fn craters(mut self, craters: usize) -> Self {
+        self.craters = craters;
+        self
+    }
+
+    /// Sets the color of the asteroid.
+    pub fn color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Generates the vertices and indexes of the asteroid,
+    /// moving every vertex of an icosphere along its normal by the height of the surface.
+    pub fn generate_vertices_and_indexes(&self) -> (Vec<Vertex>, Vec<u32>) {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let noise = Perlin::new(rng.gen());
+
+        // Asteroids are rarely round, so each axis gets stretched a bit
+        let stretch: [f32; 3] = [rng.gen_range(0.75..1.25), rng.gen_range(0.7..1.0), rng.gen_range(0.75..1.25)];
+        let noise_offset: [f32; 3] = [rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0)];
+
+        let craters: Vec<Crater> = (0..self.craters)
+            .map(|_| Crater {
+                center: random_direction(&mut rng),
+                radius: rng.gen_range(0.15..0.5),
+                depth: rng.gen_range(0.03..0.1),
+            })
+            .collect();
+
+        let (mut vertices, indices) = icosphere(1.0, self.subdivisions);
+
+        for vertex in vertices.iter_mut() {
+            let direction = vertex.normal;
+            let height = 1.0
+                + self.roughness * noise.fbm(add(scale(direction, 2.0), noise_offset), 5)
+                + craters.iter().map(|crater| crater.height(direction)).sum::<f32>();
+            vertex.position = [0, 1, 2].map(|i| direction[i] * height * stretch[i] * self.radius);
+        }
+
+        compute_normals(&mut vertices, &indices);
+        compute_tangents(&mut vertices, &indices);
+
+        (vertices, indices)
+    }
+}
+
+impl Default for AsteroidBuilder {
+    fn default() -> Self {
+        AsteroidBuilder::new()
+    }
+}
+
+impl MeshBuilder for AsteroidBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        self.generate_vertices_and_indexes()
+    }
+
+    fn material(&self) -> Material {
+        // Bare rock, without any shine
+        Material {
+            roughness: 1.0,
+            ..Material::color(self.color)
+        }
+    }
+}
+
+/// Bowl shaped dent on the surface, with a raised rim.
+struct Crater {
+    center: [f32; 3],
+    radius: f32,
+    depth: f32,
+}
+
+impl Crater {
+    /// Height added to the surface at the given direction.
+    fn height(&self, direction: [f32; 3]) -> f32 {
+        let distance = dot(direction, self.center).clamp(-1.0, 1.0).acos() / self.radius;
+        let bowl = if distance < 1.0 { distance * distance - 1.0 } else { 0.0 };
+        let rim = 0.3 * (-((distance - 1.0) / 0.3).powi(2)).exp();
+        self.depth * (bowl + rim)
+    }
+}
+
+fn random_direction(rng: &mut StdRng) -> [f32; 3] {
+    let y: f32 = rng.gen_range(-1.0..1.0);
+    let phi: f32 = rng.gen_range(0.0..std::f32::consts::TAU);
+    let h = (1.0 - y * y).sqrt();
+    [h * phi.cos(), y, h * phi.sin()]
+}
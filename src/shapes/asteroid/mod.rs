@@ -0,0 +1,3 @@
+pub use builder::AsteroidBuilder;
+
+mod builder;
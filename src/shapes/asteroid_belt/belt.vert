@@ -0,0 +1,35 @@
+#version 330
+
+#include "transform.glsl"
+
+in vec3 position;
+in vec3 normal;
+in vec2 tex_coords;
+
+// Per asteroid
+in vec3 instance_position;
+in vec3 instance_scale;
+in vec3 instance_axis;
+in float instance_phase;
+in float instance_spin;
+
+out vec3 v_normal;
+out vec2 v_tex_coords;
+
+uniform float time;
+
+// Rotates v by angle around the unit vector axis (Rodrigues' formula)
+vec3 rotate(vec3 v, vec3 axis, float angle) {
+    return v * cos(angle) + cross(axis, v) * sin(angle) + axis * dot(axis, v) * (1.0 - cos(angle));
+}
+
+void main() {
+    float angle = instance_phase + instance_spin * time;
+    vec3 local_position = rotate(position * instance_scale, instance_axis, angle);
+    // Stretching the rock bends its normals the opposite way
+    vec3 local_normal = rotate(normal / instance_scale, instance_axis, angle);
+
+    v_normal = place_normal(local_normal);
+    v_tex_coords = tex_coords;
+    gl_Position = place(instance_position + local_position);
+}
@@ -0,0 +1,127 @@
+#![allow(dead_code)]
+
+use glium::Display;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::shapes::asteroid::AsteroidBuilder;
+use crate::shapes::asteroid_belt::{AsteroidBelt, Instance};
+use crate::shapes::vectors::normalize;
+use crate::error::Result;
+
+/// Helps to create an asteroid belt.
+pub struct AsteroidBeltBuilder {
+    seed: u64,
+    count: usize,
+    inner_radius: f32,
+    outer_radius: f32,
+    thickness: f32,
+    size: (f32, f32),
+    color: [f32; 3],
+}
+
+impl AsteroidBeltBuilder {
+    /// Creates a new asteroid belt builder.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::asteroid_belt::AsteroidBeltBuilder;
+    /// let belt = AsteroidBeltBuilder::new()
+    ///  .count(5000)
+    ///  .radii(1.5, 2.0)
+    ///  .build(display)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new() -> Self {
+        AsteroidBeltBuilder {
+            seed: 0,
+            count: 2000,
+            inner_radius: 1.0,
+            outer_radius: 1.5,
+            thickness: 0.05,
+            size: (0.005, 0.02),
+            color: [0.4, 0.38, 0.35],
+        }
+    }
+
+    /// Sets the seed of the rock shape and of the placement of the asteroids.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the number of asteroids.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Sets the orbital radii the asteroids are scattered between.
+    pub fn radii(mut self, inner_radius: f32, outer_radius: f32) -> Self {
+        self.inner_radius = inner_radius;
+        self.outer_radius = outer_radius;
+        self
+    }
+
+    /// Sets how far above and below the orbital plane the asteroids may be.
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets the smallest and biggest radius of an asteroid.
+    pub fn size(mut self, min: f32, max: f32) -> Self {
+        self.size = (min.min(max), min.max(max));
+        self
+    }
+
+    /// Sets the color of the asteroids.
+    pub fn color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Builds the asteroid belt.
+    pub fn build(self, display: &Display) -> Result<AsteroidBelt> {
+        let (vertices, indices) = AsteroidBuilder::new()
+            .seed(self.seed)
+            .subdivisions(2)
+            .craters(4)
+            .generate_vertices_and_indexes();
+
+        AsteroidBelt::new(display, &vertices, &indices, &self.generate_instances(), self.color)
+    }
+
+    /// Scatters the asteroids in the torus between the two radii.
+    fn generate_instances(&self) -> Vec<Instance> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let (inner, outer) = (self.inner_radius.min(self.outer_radius), self.inner_radius.max(self.outer_radius));
+
+        (0..self.count)
+            .map(|_| {
+                // Square root keeps the asteroids evenly spread over the area of the ring
+                let radius = rng.gen_range(inner * inner..=outer * outer).sqrt();
+                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                // Sum of two random numbers, so asteroids gather around the orbital plane
+                let height = self.thickness * (rng.gen_range(-0.5..0.5) + rng.gen_range(-0.5..0.5));
+
+                let size = rng.gen_range(self.size.0..=self.size.1);
+                Instance {
+                    instance_position: [radius * angle.cos(), height, radius * angle.sin()],
+                    instance_scale: [0, 1, 2].map(|_| size * rng.gen_range(0.7..1.3)),
+                    instance_axis: normalize([rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)]),
+                    instance_phase: rng.gen_range(0.0..std::f32::consts::TAU),
+                    instance_spin: rng.gen_range(-1.5..1.5),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for AsteroidBeltBuilder {
+    fn default() -> Self {
+        AsteroidBeltBuilder::new()
+    }
+}
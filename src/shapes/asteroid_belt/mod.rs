@@ -0,0 +1,79 @@
+use std::rc::Rc;
+
+use glium::{Display, IndexBuffer, Surface, VertexBuffer};
+
+pub use builder::AsteroidBeltBuilder;
+
+use crate::render::{Drawable, RenderContext, RenderTarget};
+use crate::shapes::{Transform, Vertex};
+use crate::shapes::programs::{ProgramCache, ShaderProgram};
+use crate::shader;
+use crate::error::{Error, Result};
+
+mod builder;
+
+/// Placement of one asteroid of the belt, passed to the shader once per asteroid.
+#[derive(Debug, Clone, Copy)]
+pub struct Instance {
+    instance_position: [f32; 3],
+    instance_scale: [f32; 3],
+    /// Axis the asteroid spins around
+    instance_axis: [f32; 3],
+    /// Starting angle around the axis
+    instance_phase: f32,
+    /// Spin speed in radians per second
+    instance_spin: f32,
+}
+
+implement_vertex!(Instance, instance_position, instance_scale, instance_axis, instance_phase, instance_spin);
+
+/// Ring of asteroids sharing one rock mesh, drawn with a single instanced draw call.
+pub struct AsteroidBelt {
+    vertices: VertexBuffer<Vertex>,
+    indices: IndexBuffer<u32>,
+    instances: VertexBuffer<Instance>,
+    color: [f32; 3],
+    program: Rc<ShaderProgram>,
+}
+
+impl AsteroidBelt {
+    pub fn new(display: &Display, vertices: &[Vertex], indices: &[u32], instances: &[Instance], color: [f32; 3]) -> Result<Self> {
+        Ok(AsteroidBelt {
+            vertices: VertexBuffer::new(display, vertices)?,
+            indices: IndexBuffer::new(display, glium::index::PrimitiveType::TrianglesList, indices)?,
+            instances: VertexBuffer::new(display, instances)?,
+            color,
+            // Lit like any other mesh, only placed differently
+            program: ProgramCache::for_display(display).get(shader!("belt.vert"), shader!("../mesh/mesh.frag"), &[])?,
+        })
+    }
+}
+
+impl Drawable for AsteroidBelt {
+    fn draw(&self, target: &mut RenderTarget, context: &RenderContext, transform: &Transform) -> Result<()> {
+        let uniforms = uniform! {
+            time: context.time,
+            base_color_factor: [self.color[0], self.color[1], self.color[2], 1.0],
+            metallic: 0.0f32,
+            roughness: 1.0f32,
+            emissive: [0.0f32; 3],
+            light_direction: context.light_direction,
+            eye_direction: transform.get_eye_direction(),
+            translation: transform.get_translation(),
+            rotation: transform.get_rotation(),
+            scale: transform.get_scaling(),
+            self_rotation: transform.get_self_rotation(),
+            view: transform.get_view(),
+        };
+
+        let instances = self.instances.per_instance().map_err(|_| Error::InstancingNotSupported)?;
+        target.draw(
+            (&self.vertices, instances),
+            &self.indices,
+            &self.program.get(),
+            &uniforms,
+            &context.params,
+        )?;
+        Ok(())
+    }
+}
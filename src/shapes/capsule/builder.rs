@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+
+use crate::shapes::capsule::generate_vertices_and_indexes;
+use crate::shapes::mesh::{Material, MeshBuilder};
+use crate::shapes::Vertex;
+
+/// Helps to create a capsule along the y axis.
+pub struct CapsuleBuilder {
+    radius: f32,
+    height: f32,
+    segments: usize,
+    rings: usize,
+    color: [f32; 3],
+}
+
+impl CapsuleBuilder {
+    /// Creates a new capsule builder.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::capsule::CapsuleBuilder;
+    /// use rt::shapes::mesh::MeshBuilder;
+    /// let fuel_tank = CapsuleBuilder::new()
+    ///  .radius(0.15)
+    ///  .height(0.6)
+    ///  .build(display)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new() -> Self {
+        CapsuleBuilder {
+            radius: 0.5,
+            height: 1.0,
+            segments: 32,
+            rings: 8,
+            color: [0.8, 0.8, 0.8],
+        }
+    }
+
+    /// Sets the radius of the capsule.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the height of the cylinder between the two half spheres.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the number of steps around the axis.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the number of rings of each half sphere, from its pole to the cylinder.
+    pub fn rings(mut self, rings: usize) -> Self {
+        self.rings = rings;
+        self
+    }
+
+    /// Sets the color of the capsule.
+    pub fn color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Default for CapsuleBuilder {
+    fn default() -> Self {
+        CapsuleBuilder::new()
+    }
+}
+
+impl MeshBuilder for CapsuleBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        generate_vertices_and_indexes(self.radius, self.height, self.segments, self.rings)
+    }
+
+    fn material(&self) -> Material {
+        Material::color(self.color)
+    }
+}
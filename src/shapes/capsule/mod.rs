@@ -0,0 +1,32 @@
+use std::f32::consts::PI;
+
+use crate::shapes::Vertex;
+use crate::shapes::revolution::{revolve, ProfilePoint};
+
+mod builder;
+pub use self::builder::CapsuleBuilder;
+
+/// Function that generates vertices and indexes for a capsule standing on the y axis, centered on the origin:
+/// a cylinder of the given height closed by two half spheres.
+///
+/// v follows the length of the profile, so the texture isn't stretched over the caps.
+pub(crate) fn generate_vertices_and_indexes(radius: f32, height: f32, segments: usize, rings: usize) -> (Vec<Vertex>, Vec<u32>) {
+    let rings = rings.max(1);
+    let length = PI * radius + height;
+
+    let mut profile = Vec::with_capacity(2 * rings + 2);
+    // From the south pole to the equator of the bottom half sphere, then on from the equator of the top one
+    for (center, first_angle) in [(-height / 2.0, -PI / 2.0), (height / 2.0, 0.0)] {
+        for ring in 0..=rings {
+            let angle = first_angle + ring as f32 / rings as f32 * PI / 2.0;
+            let (sin, cos) = angle.sin_cos();
+            // The cosine of the poles isn't exactly 0, but they have to sit on the axis
+            let distance = if angle.abs() == PI / 2.0 { 0.0 } else { radius * cos };
+            // Length of the profile from the south pole
+            let arc = (angle + PI / 2.0) * radius + if center > 0.0 { height } else { 0.0 };
+            profile.push(ProfilePoint::new(distance, center + radius * sin, [cos, sin], arc / length));
+        }
+    }
+
+    revolve(&profile, segments)
+}
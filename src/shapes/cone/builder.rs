@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+use crate::shapes::cone::generate_vertices_and_indexes;
+use crate::shapes::mesh::{Material, MeshBuilder};
+use crate::shapes::Vertex;
+
+/// Helps to create a cone pointing up, or a frustum when given a top radius.
+pub struct ConeBuilder {
+    radius: f32,
+    top_radius: f32,
+    height: f32,
+    segments: usize,
+    rings: usize,
+    color: [f32; 3],
+}
+
+impl ConeBuilder {
+    /// Creates a new cone builder.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::cone::ConeBuilder;
+    /// use rt::shapes::mesh::MeshBuilder;
+    /// let nozzle = ConeBuilder::new()
+    ///  .radius(0.3)
+    ///  .top_radius(0.1)
+    ///  .height(0.4)
+    ///  .build(display)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new() -> Self {
+        ConeBuilder {
+            radius: 1.0,
+            top_radius: 0.0,
+            height: 1.0,
+            segments: 32,
+            rings: 1,
+            color: [0.8, 0.8, 0.8],
+        }
+    }
+
+    /// Sets the radius of the base.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the radius of the top, 0 for a pointed cone.
+    pub fn top_radius(mut self, top_radius: f32) -> Self {
+        self.top_radius = top_radius;
+        self
+    }
+
+    /// Sets the height of the cone, along y.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the number of steps around the axis.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the number of rings the side is split in along the axis.
+    pub fn rings(mut self, rings: usize) -> Self {
+        self.rings = rings;
+        self
+    }
+
+    /// Sets the color of the cone.
+    pub fn color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Default for ConeBuilder {
+    fn default() -> Self {
+        ConeBuilder::new()
+    }
+}
+
+impl MeshBuilder for ConeBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        generate_vertices_and_indexes(self.radius, self.top_radius, self.height, self.segments, self.rings)
+    }
+
+    fn material(&self) -> Material {
+        Material::color(self.color)
+    }
+}
@@ -0,0 +1,37 @@
+use crate::shapes::{disc, Vertex};
+use crate::shapes::mesh::merge_geometry;
+use crate::shapes::revolution::{revolve, ProfilePoint};
+use crate::shapes::vectors::length;
+
+mod builder;
+pub use self::builder::ConeBuilder;
+
+/// Function that generates vertices and indexes for a cone standing on the y axis, centered on the origin,
+/// cut into a frustum when the top radius is not 0.
+///
+/// The side is wrapped once by the texture, the ends get it laid flat like a disc.
+pub(crate) fn generate_vertices_and_indexes(radius: f32, top_radius: f32, height: f32, segments: usize, rings: usize) -> (Vec<Vertex>, Vec<u32>) {
+    let rings = rings.max(1);
+    let top_radius = top_radius.max(0.0);
+
+    // Perpendicular to the slope of the side
+    let slope = [height, radius - top_radius];
+    let normal = slope.map(|n| n / length([slope[0], slope[1], 0.0]));
+
+    let profile: Vec<ProfilePoint> = (0..=rings)
+        .map(|ring| {
+            let v = ring as f32 / rings as f32;
+            let ring_radius = if ring == rings { top_radius } else { radius + (top_radius - radius) * v };
+            ProfilePoint::new(ring_radius, height * (v - 0.5), normal, v)
+        })
+        .collect();
+
+    let mut parts = vec![
+        revolve(&profile, segments),
+        disc::generate_vertices_and_indexes(radius, 0.0, segments, 1, -height / 2.0, false),
+    ];
+    if top_radius > 0.0 {
+        parts.push(disc::generate_vertices_and_indexes(top_radius, 0.0, segments, 1, height / 2.0, true));
+    }
+    merge_geometry(parts)
+}
@@ -1,5 +1,12 @@
+use std::rc::Rc;
+
 use glium::Display;
-use crate::shapes::cube::Cube;
+use glium::index::PrimitiveType;
+
+use crate::error::Result;
+use crate::shapes::cube::{generate_indices, generate_vertices};
+use crate::shapes::mesh::{Material, Mesh, MeshBuilder};
+use crate::shapes::Vertex;
 
 pub struct CubeBuilder {
     origin: [f32; 3],
@@ -31,7 +38,32 @@ impl CubeBuilder {
         self
     }
 
-    pub fn build(self, display: &Display) -> Cube {
-        Cube::new(display, self.origin, self.size, self.color)
+    /// Builds the red outline of every face, to draw over the cube.
+    pub fn build_wireframe(&self, display: &Display) -> Result<Mesh> {
+        let (_, wireframe_indices) = generate_indices();
+        Mesh::with_primitive(
+            display,
+            &generate_vertices(self.origin, self.size),
+            &wireframe_indices,
+            PrimitiveType::LinesList,
+            Rc::new(Material::unlit([1.0, 0.0, 0.0])),
+        )
+    }
+}
+
+impl Default for CubeBuilder {
+    fn default() -> Self {
+        CubeBuilder::new()
     }
-}
\ No newline at end of file
+}
+
+impl MeshBuilder for CubeBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        let (indices, _) = generate_indices();
+        (generate_vertices(self.origin, self.size), indices)
+    }
+
+    fn material(&self) -> Material {
+        Material::unlit(self.color)
+    }
+}
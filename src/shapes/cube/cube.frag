@@ -1,9 +0,0 @@
-#version 330
-
-uniform vec3 color;
-
-out vec4 fragColor;
-
-void main() {
-    fragColor = vec4(color, 1.0);
-}
@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+use crate::shapes::cylinder::generate_vertices_and_indexes;
+use crate::shapes::mesh::{Material, MeshBuilder};
+use crate::shapes::Vertex;
+
+/// Helps to create a cylinder, closed or open at both ends.
+pub struct CylinderBuilder {
+    radius: f32,
+    height: f32,
+    segments: usize,
+    rings: usize,
+    caps: bool,
+    color: [f32; 3],
+}
+
+impl CylinderBuilder {
+    /// Creates a new cylinder builder.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::cylinder::CylinderBuilder;
+    /// use rt::shapes::mesh::MeshBuilder;
+    /// let hull = CylinderBuilder::new()
+    ///  .radius(0.2)
+    ///  .height(1.5)
+    ///  .segments(24)
+    ///  .build(display)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new() -> Self {
+        CylinderBuilder {
+            radius: 1.0,
+            height: 1.0,
+            segments: 32,
+            rings: 1,
+            caps: true,
+            color: [0.8, 0.8, 0.8],
+        }
+    }
+
+    /// Sets the radius of the cylinder.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the height of the cylinder, along y.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the number of steps around the axis.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the number of rings the side is split in along the axis.
+    pub fn rings(mut self, rings: usize) -> Self {
+        self.rings = rings;
+        self
+    }
+
+    /// Sets whether both ends are closed, leaving a tube when they aren't.
+    pub fn caps(mut self, caps: bool) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    /// Sets the color of the cylinder.
+    pub fn color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Default for CylinderBuilder {
+    fn default() -> Self {
+        CylinderBuilder::new()
+    }
+}
+
+impl MeshBuilder for CylinderBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        generate_vertices_and_indexes(self.radius, self.height, self.segments, self.rings, self.caps)
+    }
+
+    fn material(&self) -> Material {
+        Material::color(self.color)
+    }
+}
@@ -0,0 +1,29 @@
+use crate::shapes::{disc, Vertex};
+use crate::shapes::mesh::merge_geometry;
+use crate::shapes::revolution::{revolve, ProfilePoint};
+
+mod builder;
+pub use self::builder::CylinderBuilder;
+
+/// Function that generates vertices and indexes for a cylinder standing on the y axis, centered on the origin.
+///
+/// The side is wrapped once by the texture, the caps get it laid flat like a disc.
+pub(crate) fn generate_vertices_and_indexes(radius: f32, height: f32, segments: usize, rings: usize, caps: bool) -> (Vec<Vertex>, Vec<u32>) {
+    let rings = rings.max(1);
+    let profile: Vec<ProfilePoint> = (0..=rings)
+        .map(|ring| {
+            let v = ring as f32 / rings as f32;
+            ProfilePoint::new(radius, height * (v - 0.5), [1.0, 0.0], v)
+        })
+        .collect();
+    let side = revolve(&profile, segments);
+
+    if !caps {
+        return side;
+    }
+    merge_geometry([
+        side,
+        disc::generate_vertices_and_indexes(radius, 0.0, segments, 1, height / 2.0, true),
+        disc::generate_vertices_and_indexes(radius, 0.0, segments, 1, -height / 2.0, false),
+    ])
+}
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+
+use crate::shapes::disc::generate_vertices_and_indexes;
+use crate::shapes::mesh::{Material, MeshBuilder};
+use crate::shapes::Vertex;
+
+/// Helps to create a flat disc facing up, or an annulus when given an inner radius.
+pub struct DiscBuilder {
+    radius: f32,
+    inner_radius: f32,
+    segments: usize,
+    rings: usize,
+    color: [f32; 3],
+}
+
+impl DiscBuilder {
+    /// Creates a new disc builder.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::disc::DiscBuilder;
+    /// use rt::shapes::mesh::MeshBuilder;
+    /// let docking_ring = DiscBuilder::new()
+    ///  .radius(1.0)
+    ///  .inner_radius(0.8)
+    ///  .build(display)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new() -> Self {
+        DiscBuilder {
+            radius: 1.0,
+            inner_radius: 0.0,
+            segments: 32,
+            rings: 1,
+            color: [0.8, 0.8, 0.8],
+        }
+    }
+
+    /// Sets the radius of the disc.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the radius of the hole in the middle, 0 for a full disc.
+    pub fn inner_radius(mut self, inner_radius: f32) -> Self {
+        self.inner_radius = inner_radius;
+        self
+    }
+
+    /// Sets the number of steps around the center.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the number of rings from the inner to the outer radius.
+    pub fn rings(mut self, rings: usize) -> Self {
+        self.rings = rings;
+        self
+    }
+
+    /// Sets the color of the disc.
+    pub fn color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Default for DiscBuilder {
+    fn default() -> Self {
+        DiscBuilder::new()
+    }
+}
+
+impl MeshBuilder for DiscBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        generate_vertices_and_indexes(self.radius, self.inner_radius, self.segments, self.rings, 0.0, true)
+    }
+
+    fn material(&self) -> Material {
+        Material::color(self.color)
+    }
+}
@@ -0,0 +1,78 @@
+use std::f32::consts::TAU;
+
+use crate::shapes::Vertex;
+
+mod builder;
+pub use self::builder::DiscBuilder;
+
+/// Function that generates vertices and indexes for a flat disc at the given height,
+/// facing up or down, with a hole in the middle when the inner radius is not 0.
+///
+/// The texture is laid flat over the square around the disc, with u along x,
+/// so a disc facing down shows it mirrored compared to one facing up.
+pub(crate) fn generate_vertices_and_indexes(
+    outer_radius: f32,
+    inner_radius: f32,
+    segments: usize,
+    rings: usize,
+    height: f32,
+    facing_up: bool,
+) -> (Vec<Vertex>, Vec<u32>) {
+    let segments = segments.max(3);
+    let rings = rings.max(1);
+    let inner_radius = inner_radius.clamp(0.0, outer_radius);
+    let closed = inner_radius == 0.0;
+
+    let normal = if facing_up { [0.0, 1.0, 0.0] } else { [0.0, -1.0, 0.0] };
+    // v grows along the normal crossed with the tangent
+    let v_sign = if facing_up { -1.0 } else { 1.0 };
+    let vertex = |x: f32, z: f32| Vertex::new(
+        [x, height, z],
+        normal,
+        [1.0, 0.0, 0.0],
+        [0.5 + x / (2.0 * outer_radius), 0.5 + v_sign * z / (2.0 * outer_radius)],
+    );
+
+    let mut vertices = Vec::with_capacity((rings + 1) * segments);
+    for ring in 0..=rings {
+        let radius = inner_radius + (outer_radius - inner_radius) * ring as f32 / rings as f32;
+        if ring == 0 && closed {
+            vertices.push(vertex(0.0, 0.0));
+            continue;
+        }
+        for segment in 0..segments {
+            let (sin, cos) = (segment as f32 / segments as f32 * TAU).sin_cos();
+            vertices.push(vertex(radius * sin, radius * cos));
+        }
+    }
+
+    // Index of a vertex of a ring, the center counting as a ring of its own
+    let index = |ring: usize, segment: usize| -> u32 {
+        let first = if closed && ring > 0 { 1 + (ring - 1) * segments } else { ring * segments };
+        (first + segment % segments) as u32
+    };
+
+    let mut indices = Vec::with_capacity(rings * segments * 6);
+    for ring in 0..rings {
+        for segment in 0..segments {
+            let outer = index(ring + 1, segment);
+            let outer_next = index(ring + 1, segment + 1);
+            if ring == 0 && closed {
+                indices.extend_from_slice(&[0, outer, outer_next]);
+            } else {
+                let inner = index(ring, segment);
+                let inner_next = index(ring, segment + 1);
+                indices.extend_from_slice(&[inner, outer, outer_next]);
+                indices.extend_from_slice(&[inner, outer_next, inner_next]);
+            }
+        }
+    }
+
+    if !facing_up {
+        for triangle in indices.chunks_mut(3) {
+            triangle.swap(1, 2);
+        }
+    }
+
+    (vertices, indices)
+}
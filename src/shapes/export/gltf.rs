@@ -0,0 +1,161 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use glium::index::PrimitiveType;
+use serde_json::{json, Value};
+
+use crate::shapes::export::{ExportNode, MeshData};
+
+// Values defined by the glTF specification
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+/// Writes the nodes to a glTF file, each with its matrix,
+/// and the geometry to a binary file with the same name.
+/// Gives back the meshes left out, which glTF can't hold.
+pub(super) fn write(nodes: &[ExportNode], path: &Path) -> io::Result<Vec<String>> {
+    let bin_path = path.with_extension("bin");
+    let bin_name = bin_path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+
+    let mut document = Document::default();
+    let mut gltf_nodes = Vec::new();
+    let mut skipped = Vec::new();
+
+    for node in nodes {
+        let primitives: Vec<Value> = node.meshes.iter()
+            .enumerate()
+            .filter_map(|(i, mesh)| {
+                let primitive = document.add_primitive(mesh);
+                if primitive.is_none() {
+                    skipped.push(format!("Skipped the mesh {} of {}: {:?} can't be exported", i, node.name, mesh.primitive));
+                }
+                primitive
+            })
+            .collect();
+
+        gltf_nodes.push(json!({
+            "name": node.name,
+            "matrix": node.matrix.iter().flatten().collect::<Vec<_>>(),
+            "mesh": document.meshes.len(),
+        }));
+        document.meshes.push(json!({ "name": node.name, "primitives": primitives }));
+    }
+
+    let gltf = json!({
+        "asset": { "version": "2.0", "generator": "space scene exporter" },
+        "scene": 0,
+        "scenes": [{ "nodes": (0..gltf_nodes.len()).collect::<Vec<_>>() }],
+        "nodes": gltf_nodes,
+        "meshes": document.meshes,
+        "materials": document.materials,
+        "accessors": document.accessors,
+        "bufferViews": document.buffer_views,
+        "buffers": [{ "byteLength": document.buffer.len(), "uri": bin_name }],
+    });
+
+    fs::write(&bin_path, &document.buffer)?;
+    fs::write(path, serde_json::to_string_pretty(&gltf)?)?;
+    Ok(skipped)
+}
+
+/// Parts of the glTF file filled while going through the meshes.
+#[derive(Default)]
+struct Document {
+    buffer: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+    materials: Vec<Value>,
+    meshes: Vec<Value>,
+}
+
+impl Document {
+    /// Adds the geometry and material of a mesh, giving back its primitive,
+    /// or nothing for the primitive types glTF has no mode for.
+    fn add_primitive(&mut self, mesh: &MeshData) -> Option<Value> {
+        let mode = match mesh.primitive {
+            PrimitiveType::LinesList => 1,
+            PrimitiveType::LineLoop => 2,
+            PrimitiveType::TrianglesList => 4,
+            _ => return None,
+        };
+
+        let positions: Vec<[f32; 3]> = mesh.vertices.iter().map(|v| v.position).collect();
+        // glTF puts v = 0 at the top of the image, our textures at the bottom
+        let tex_coords: Vec<[f32; 2]> = mesh.vertices.iter().map(|v| [v.tex_coords[0], 1.0 - v.tex_coords[1]]).collect();
+
+        let mut attributes = json!({
+            "POSITION": self.add_floats(&positions, "VEC3", true),
+            "TEXCOORD_0": self.add_floats(&tex_coords, "VEC2", false),
+        });
+        // Points and lines have no surface to take normals from
+        if mode == 4 {
+            let normals: Vec<[f32; 3]> = mesh.vertices.iter().map(|v| v.normal).collect();
+            let tangents: Vec<[f32; 4]> = mesh.vertices.iter().map(|v| [v.tangent[0], v.tangent[1], v.tangent[2], 1.0]).collect();
+            attributes["NORMAL"] = self.add_floats(&normals, "VEC3", false).into();
+            attributes["TANGENT"] = self.add_floats(&tangents, "VEC4", false).into();
+        }
+
+        let indices = self.add_accessor(
+            bytes(mesh.indices.iter().flat_map(|i| i.to_le_bytes())),
+            ELEMENT_ARRAY_BUFFER,
+            json!({ "componentType": UNSIGNED_INT, "count": mesh.indices.len(), "type": "SCALAR" }),
+        );
+
+        self.materials.push(json!({
+            "pbrMetallicRoughness": {
+                "baseColorFactor": [mesh.color[0], mesh.color[1], mesh.color[2], 1.0],
+                "metallicFactor": 0.0,
+                "roughnessFactor": 1.0,
+            },
+        }));
+
+        Some(json!({
+            "attributes": attributes,
+            "indices": indices,
+            "material": self.materials.len() - 1,
+            "mode": mode,
+        }))
+    }
+
+    /// Adds a list of float vectors, with their bounds when asked (required for positions).
+    fn add_floats<const N: usize>(&mut self, values: &[[f32; N]], kind: &str, bounds: bool) -> usize {
+        let mut accessor = json!({ "componentType": FLOAT, "count": values.len(), "type": kind });
+        if bounds && !values.is_empty() {
+            let min: Vec<f32> = (0..N).map(|i| values.iter().map(|v| v[i]).fold(f32::MAX, f32::min)).collect();
+            let max: Vec<f32> = (0..N).map(|i| values.iter().map(|v| v[i]).fold(f32::MIN, f32::max)).collect();
+            accessor["min"] = min.into();
+            accessor["max"] = max.into();
+        }
+        self.add_accessor(
+            bytes(values.iter().flatten().flat_map(|f| f.to_le_bytes())),
+            ARRAY_BUFFER,
+            accessor,
+        )
+    }
+
+    /// Appends data to the buffer, in a view of its own, and adds the accessor reading it.
+    fn add_accessor(&mut self, data: Vec<u8>, target: u32, mut accessor: Value) -> usize {
+        // Accessors must start at a multiple of their component size
+        while !self.buffer.len().is_multiple_of(4) {
+            self.buffer.push(0);
+        }
+        self.buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": self.buffer.len(),
+            "byteLength": data.len(),
+            "target": target,
+        }));
+        self.buffer.extend(data);
+
+        accessor["bufferView"] = (self.buffer_views.len() - 1).into();
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+}
+
+fn bytes(iter: impl Iterator<Item = u8>) -> Vec<u8> {
+    iter.collect()
+}
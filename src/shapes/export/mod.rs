@@ -0,0 +1,91 @@
+use std::io;
+use std::path::Path;
+
+use glium::index::PrimitiveType;
+
+use crate::shapes::{Transform, Vertex};
+
+mod gltf;
+mod obj;
+
+/// Geometry of a shape kept on the CPU, so it can be written to a file.
+#[derive(Debug, Clone)]
+pub struct MeshData {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    /// Either `TrianglesList`, `LinesList` or `LineLoop`
+    pub primitive: PrimitiveType,
+    pub color: [f32; 3],
+}
+
+/// Interface for shapes whose geometry can be exported.
+pub trait Exportable {
+    fn mesh_data(&self) -> Vec<MeshData>;
+}
+
+/// File formats the scene can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    /// glTF 2.0, with the geometry in a `.bin` file next to the `.gltf` file
+    Gltf,
+    /// Wavefront OBJ, with the colors in a `.mtl` file next to the `.obj` file
+    Obj,
+}
+
+/// Shape placed in an exported scene.
+struct ExportNode {
+    name: String,
+    meshes: Vec<MeshData>,
+    /// Column-major matrix placing the shape in the world
+    matrix: [[f32; 4]; 4],
+}
+
+/// Collects shapes with their transforms, to write them to a file as a scene.
+/// A single shape added with `Transform::default()` is written as it was generated.
+///
+/// # Example
+/// ```no_run
+/// # fn example(display: &glium::Display) -> Result<(), Box<dyn std::error::Error>> {
+/// use rt::Transform;
+/// use rt::shapes::export::{ExportFormat, SceneExport};
+/// use rt::shapes::mesh::MeshBuilder;
+/// use rt::shapes::sphere::SphereBuilder;
+/// let earth = SphereBuilder::new().build(display)?;
+/// let earth_transform = Transform { scale: 0.3, ..Default::default() };
+///
+/// let mut scene = SceneExport::new();
+/// scene.add("earth", &earth, &earth_transform);
+/// let skipped = scene.write("scene.gltf", ExportFormat::Gltf)?;
+/// assert!(skipped.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct SceneExport {
+    nodes: Vec<ExportNode>,
+}
+
+impl SceneExport {
+    pub fn new() -> Self {
+        SceneExport::default()
+    }
+
+    /// Adds a shape placed by a transform. The view of the transform is left out.
+    pub fn add(&mut self, name: &str, shape: &impl Exportable, transform: &Transform) {
+        self.nodes.push(ExportNode {
+            name: name.to_string(),
+            meshes: shape.mesh_data(),
+            matrix: transform.get_model(),
+        });
+    }
+
+    /// Writes the scene in the given format, giving back what the format could not hold,
+    /// such as a mesh of points, which is left out of the file.
+    pub fn write(&self, path: impl AsRef<Path>, format: ExportFormat) -> io::Result<Vec<String>> {
+        match format {
+            ExportFormat::Gltf => gltf::write(&self.nodes, path.as_ref()),
+            ExportFormat::Obj => obj::write(&self.nodes, path.as_ref()),
+        }
+    }
+}
+
@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use glium::index::PrimitiveType;
+
+use crate::shapes::export::{ExportNode, MeshData};
+use crate::shapes::matrices::{transform_direction, transform_point};
+use crate::shapes::vectors::normalize;
+
+/// Writes the nodes to an OBJ file, with the vertices already placed in the world,
+/// and their colors to an MTL file with the same name.
+/// Gives back the meshes left out, which OBJ can't hold.
+pub(super) fn write(nodes: &[ExportNode], path: &Path) -> io::Result<Vec<String>> {
+    let mtl_path = path.with_extension("mtl");
+    let mtl_name = mtl_path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+
+    let mut obj = BufWriter::new(File::create(path)?);
+    let mut mtl = BufWriter::new(File::create(&mtl_path)?);
+
+    writeln!(obj, "# Exported from the space scene")?;
+    writeln!(obj, "mtllib {mtl_name}")?;
+
+    // OBJ indices start at 1 and count every vertex written before
+    let mut first_index = 1;
+    let mut skipped = Vec::new();
+    for node in nodes {
+        writeln!(obj, "o {}", node.name)?;
+
+        for (i, mesh) in node.meshes.iter().enumerate() {
+            let Some(elements) = elements(mesh, first_index) else {
+                skipped.push(format!("Skipped the mesh {} of {}: {:?} can't be exported", i, node.name, mesh.primitive));
+                continue;
+            };
+
+            let material = format!("{}_{i}", node.name);
+            writeln!(mtl, "newmtl {material}")?;
+            writeln!(mtl, "Kd {} {} {}", mesh.color[0], mesh.color[1], mesh.color[2])?;
+            writeln!(mtl)?;
+
+            for vertex in &mesh.vertices {
+                let [x, y, z] = transform_point(&node.matrix, vertex.position);
+                writeln!(obj, "v {x} {y} {z}")?;
+            }
+            for vertex in &mesh.vertices {
+                writeln!(obj, "vt {} {}", vertex.tex_coords[0], vertex.tex_coords[1])?;
+            }
+            for vertex in &mesh.vertices {
+                let [x, y, z] = normalize(transform_direction(&node.matrix, vertex.normal));
+                writeln!(obj, "vn {x} {y} {z}")?;
+            }
+
+            writeln!(obj, "usemtl {material}")?;
+            for element in elements {
+                writeln!(obj, "{element}")?;
+            }
+
+            first_index += mesh.vertices.len() as u32;
+        }
+    }
+
+    obj.flush()?;
+    mtl.flush()?;
+    Ok(skipped)
+}
+
+/// The face or line elements of a mesh, its indexes shifted by `first_index`,
+/// or nothing for the primitive types OBJ has no element for.
+fn elements(mesh: &MeshData, first_index: u32) -> Option<Vec<String>> {
+    let index = |i: u32| i + first_index;
+    match mesh.primitive {
+        PrimitiveType::TrianglesList => Some(mesh.indices.chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|k| index(triangle[k]));
+                format!("f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}")
+            })
+            .collect()),
+        PrimitiveType::LinesList => Some(mesh.indices.chunks_exact(2)
+            .map(|line| format!("l {} {}", index(line[0]), index(line[1])))
+            .collect()),
+        PrimitiveType::LineLoop => {
+            let points: Vec<String> = mesh.indices.iter()
+                .chain(mesh.indices.first())
+                .map(|&i| index(i).to_string())
+                .collect();
+            Some(vec![format!("l {}", points.join(" "))])
+        }
+        _ => None,
+    }
+}
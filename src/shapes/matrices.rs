@@ -158,3 +158,28 @@ pub fn view_matrix(position: &[f32; 3], direction: &[f32; 3], up: &[f32; 3]) ->
         [p[0], p[1], p[2], 1.0],
     ]
 }
+
+/// Function that multiplies two column-major matrices, so that `a` is applied after `b`
+pub fn multiply(a: &[[f32; 4]; 4], b: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
+    let mut result = [[0.0; 4]; 4];
+    for (column, result_column) in result.iter_mut().enumerate() {
+        for (row, value) in result_column.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[k][row] * b[column][k]).sum();
+        }
+    }
+    result
+}
+
+/// Function that applies a column-major matrix to a point
+pub fn transform_point(matrix: &[[f32; 4]; 4], point: [f32; 3]) -> [f32; 3] {
+    [0, 1, 2].map(|row| {
+        matrix[0][row] * point[0] + matrix[1][row] * point[1] + matrix[2][row] * point[2] + matrix[3][row]
+    })
+}
+
+/// Function that applies a column-major matrix to a direction, leaving out the translation
+pub fn transform_direction(matrix: &[[f32; 4]; 4], direction: [f32; 3]) -> [f32; 3] {
+    [0, 1, 2].map(|row| {
+        matrix[0][row] * direction[0] + matrix[1][row] * direction[1] + matrix[2][row] * direction[2]
+    })
+}
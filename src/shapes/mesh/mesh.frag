@@ -0,0 +1,29 @@
+#version 330
+
+// Features, defined by the material:
+// HAS_TEXTURE multiplies the base color by the texture,
+// UNLIT shows the base color as it is
+
+uniform vec4 base_color_factor;
+#ifdef HAS_TEXTURE
+uniform sampler2D tex;
+#endif
+
+in vec3 v_normal;
+in vec2 v_tex_coords;
+out vec4 fragColor;
+
+#include "lighting.glsl"
+
+void main() {
+    vec4 base_color = base_color_factor;
+#ifdef HAS_TEXTURE
+    base_color *= texture(tex, v_tex_coords);
+#endif
+
+#ifdef UNLIT
+    fragColor = base_color;
+#else
+    fragColor = vec4(shade(base_color.rgb, v_normal), base_color.a);
+#endif
+}
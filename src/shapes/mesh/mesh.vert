@@ -0,0 +1,18 @@
+#version 330
+
+#include "transform.glsl"
+
+in vec3 position;
+in vec3 normal;
+in vec2 tex_coords;
+out vec3 v_normal;
+out vec2 v_tex_coords;
+
+uniform mat4 model;
+
+void main() {
+    v_tex_coords = tex_coords;
+    // The inverse transpose undoes the stretching of the model matrix
+    v_normal = place_normal(transpose(inverse(mat3(model))) * normal);
+    gl_Position = place((model * vec4(position, 1.0)).xyz);
+}
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use glium::{Display, DrawParameters, IndexBuffer, Surface, VertexBuffer};
+use glium::index::PrimitiveType;
+use glium::texture::SrgbTexture2d;
+
+use crate::{identity, shader};
+use crate::error::Result;
+use crate::render::{BlendMode, Drawable, RenderContext, RenderTarget};
+use crate::shapes::{Transform, Vertex};
+use crate::shapes::export::{Exportable, MeshData};
+use crate::shapes::programs::{ProgramCache, ShaderProgram};
+use crate::shapes::vectors::{add, cross, dot, length, normalize, scale, sub};
+
+/// Metallic-roughness surface of a mesh.
+/// The base color is multiplied by the texture when there is one.
+#[derive(Debug, Clone)]
+pub struct Material {
+    pub base_color: [f32; 4],
+    pub texture: Option<Rc<SrgbTexture2d>>,
+    pub metallic: f32,
+    pub roughness: f32,
+    /// Light given off by the surface itself
+    pub emissive: [f32; 3],
+    /// Shows the base color as it is, without any lighting
+    pub unlit: bool,
+    /// Mixes the mesh with what is behind it, for meshes drawn in the transparent pass
+    pub blend: Option<BlendMode>,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Material {
+            base_color: [0.8, 0.8, 0.8, 1.0],
+            texture: None,
+            metallic: 0.0,
+            roughness: 0.8,
+            emissive: [0.0; 3],
+            unlit: false,
+            blend: None,
+        }
+    }
+}
+
+impl Material {
+    /// Flat color, not affected by the light.
+    pub fn unlit(color: [f32; 3]) -> Self {
+        Material {
+            base_color: [color[0], color[1], color[2], 1.0],
+            unlit: true,
+            ..Default::default()
+        }
+    }
+
+    /// Color lit by the scene, with the default roughness.
+    pub fn color(color: [f32; 3]) -> Self {
+        Material {
+            base_color: [color[0], color[1], color[2], 1.0],
+            ..Default::default()
+        }
+    }
+}
+
+/// Interface for the builders of shapes drawn as a single mesh.
+/// Shapes only have to generate their geometry, the mesh takes care of the buffers and the program.
+pub trait MeshBuilder {
+    /// Generates the vertices and the indexes of the triangles of the shape.
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>);
+
+    /// Material the shape is drawn with.
+    fn material(&self) -> Material;
+
+    /// Builds the mesh of the shape.
+    fn build(&self, display: &Display) -> Result<Mesh> {
+        let (vertices, indices) = self.geometry();
+        Mesh::new(display, &vertices, &indices, Rc::new(self.material()))
+    }
+}
+
+/// Primitives sharing one material.
+/// The vertices and indices are kept on the CPU, next to their GPU buffers, to be read back or exported.
+pub struct Mesh {
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+    primitive: PrimitiveType,
+    vertex_buffer: VertexBuffer<Vertex>,
+    index_buffer: IndexBuffer<u32>,
+    material: Rc<Material>,
+    program: Rc<ShaderProgram>,
+}
+
+impl Mesh {
+    /// Creates a mesh of triangles.
+    pub fn new(display: &Display, vertices: &[Vertex], indices: &[u32], material: Rc<Material>) -> Result<Self> {
+        Mesh::with_primitive(display, vertices, indices, PrimitiveType::TrianglesList, material)
+    }
+
+    /// Creates a mesh of any primitive, such as lines, which are best drawn with an unlit material.
+    pub fn with_primitive(
+        display: &Display,
+        vertices: &[Vertex],
+        indices: &[u32],
+        primitive: PrimitiveType,
+        material: Rc<Material>,
+    ) -> Result<Self> {
+        let mut defines = Vec::new();
+        if material.texture.is_some() {
+            defines.push(("HAS_TEXTURE", "1"));
+        }
+        if material.unlit {
+            defines.push(("UNLIT", "1"));
+        }
+
+        Ok(Mesh {
+            vertices: vertices.to_vec(),
+            indices: indices.to_vec(),
+            primitive,
+            vertex_buffer: VertexBuffer::new(display, vertices)?,
+            index_buffer: IndexBuffer::new(display, primitive, indices)?,
+            material,
+            program: ProgramCache::for_display(display).get(shader!("mesh.vert"), shader!("mesh.frag"), &defines)?,
+        })
+    }
+
+    /// Distance from the origin to the farthest vertex.
+    pub fn bounding_radius(&self) -> f32 {
+        self.vertices.iter()
+            .map(|vertex| length(vertex.position))
+            .fold(0.0, f32::max)
+    }
+
+    /// Draws the mesh placed by a model matrix, applied before the transform.
+    pub fn draw_with_matrix(
+        &self,
+        target: &mut RenderTarget,
+        context: &RenderContext,
+        transform: &Transform,
+        model: [[f32; 4]; 4],
+    ) -> Result<()> {
+        let uniforms = uniform! {
+            base_color_factor: self.material.base_color,
+            metallic: self.material.metallic,
+            roughness: self.material.roughness,
+            emissive: self.material.emissive,
+            light_direction: context.light_direction,
+            eye_direction: transform.get_eye_direction(),
+            model: model,
+            translation: transform.get_translation(),
+            rotation: transform.get_rotation(),
+            scale: transform.get_scaling(),
+            self_rotation: transform.get_self_rotation(),
+            view: transform.get_view(),
+        };
+        let params = match self.material.blend {
+            Some(mode) => DrawParameters { blend: mode.blend(), ..context.params.clone() },
+            None => context.params.clone(),
+        };
+
+        match &self.material.texture {
+            Some(texture) => target.draw(
+                &self.vertex_buffer,
+                &self.index_buffer,
+                &self.program.get(),
+                &uniforms.add("tex", &**texture),
+                &params,
+            ),
+            None => target.draw(
+                &self.vertex_buffer,
+                &self.index_buffer,
+                &self.program.get(),
+                &uniforms,
+                &params,
+            ),
+        }?;
+        Ok(())
+    }
+}
+
+impl Drawable for Mesh {
+    fn draw(&self, target: &mut RenderTarget, context: &RenderContext, transform: &Transform) -> Result<()> {
+        self.draw_with_matrix(target, context, transform, identity!())
+    }
+}
+
+impl Exportable for Mesh {
+    fn mesh_data(&self) -> Vec<MeshData> {
+        let [r, g, b, _] = self.material.base_color;
+        vec![MeshData {
+            vertices: self.vertices.clone(),
+            indices: self.indices.clone(),
+            primitive: self.primitive,
+            color: [r, g, b],
+        }]
+    }
+}
+
+/// Replaces the normals by the average of the normals of the triangles around each vertex,
+/// weighted by their area.
+/// Vertices at the same position share their normal, so seams in the texture don't show.
+/// Vertices no triangle uses keep the normal they had, and indexes left over after the last
+/// whole triangle are ignored.
+pub fn compute_normals(vertices: &mut [Vertex], indices: &[u32]) {
+    let key = |vertex: &Vertex| vertex.position.map(f32::to_bits);
+    let mut normals: HashMap<[u32; 3], [f32; 3]> = HashMap::new();
+
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| vertices[triangle[i] as usize].position);
+        // Not normalized, so bigger triangles count more
+        let face_normal = cross(sub(b, a), sub(c, a));
+        for &index in triangle {
+            let normal = normals.entry(key(&vertices[index as usize])).or_insert([0.0; 3]);
+            *normal = add(*normal, face_normal);
+        }
+    }
+
+    for vertex in vertices.iter_mut() {
+        if let Some(&normal) = normals.get(&key(vertex)) {
+            vertex.normal = normalize(normal);
+        }
+    }
+}
+
+/// Sets the tangents to the direction in which u grows over the triangles around each vertex,
+/// made perpendicular to the normal. Indexes left over after the last whole triangle are ignored.
+pub fn compute_tangents(vertices: &mut [Vertex], indices: &[u32]) {
+    let mut tangents = vec![[0.0f32; 3]; vertices.len()];
+
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| vertices[triangle[i] as usize]);
+        let (edge1, edge2) = (sub(b.position, a.position), sub(c.position, a.position));
+        let (du1, dv1) = (b.tex_coords[0] - a.tex_coords[0], b.tex_coords[1] - a.tex_coords[1]);
+        let (du2, dv2) = (c.tex_coords[0] - a.tex_coords[0], c.tex_coords[1] - a.tex_coords[1]);
+
+        let determinant = du1 * dv2 - du2 * dv1;
+        if determinant.abs() < f32::EPSILON {
+            continue;
+        }
+        let tangent = scale(sub(scale(edge1, dv2), scale(edge2, dv1)), 1.0 / determinant);
+        for &index in triangle {
+            tangents[index as usize] = add(tangents[index as usize], tangent);
+        }
+    }
+
+    for (vertex, tangent) in vertices.iter_mut().zip(tangents) {
+        let normal = vertex.normal;
+        // Without texture coordinates any direction along the surface will do
+        let tangent = if length(tangent) > 0.0 {
+            tangent
+        } else if normal[1].abs() < 0.9 {
+            cross([0.0, 1.0, 0.0], normal)
+        } else {
+            cross(normal, [1.0, 0.0, 0.0])
+        };
+        vertex.tangent = normalize(sub(tangent, scale(normal, dot(tangent, normal))));
+    }
+}
+
+/// Puts the geometry of several parts together, shifting the indexes of each part after the previous ones.
+pub fn merge_geometry(parts: impl IntoIterator<Item = (Vec<Vertex>, Vec<u32>)>) -> (Vec<Vertex>, Vec<u32>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    for (part_vertices, part_indices) in parts {
+        let offset = vertices.len() as u32;
+        indices.extend(part_indices.into_iter().map(|i| i + offset));
+        vertices.extend(part_vertices);
+    }
+    (vertices, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_normals_keeps_the_normal_of_unused_vertices() {
+        let mut vertices = vec![
+            Vertex::new([0.0, 0.0, 0.0], [0.0; 3], [1.0, 0.0, 0.0], [0.0, 0.0]),
+            Vertex::new([1.0, 0.0, 0.0], [0.0; 3], [1.0, 0.0, 0.0], [1.0, 0.0]),
+            Vertex::new([0.0, 1.0, 0.0], [0.0; 3], [1.0, 0.0, 0.0], [0.0, 1.0]),
+            // Used by no triangle
+            Vertex::new([5.0, 5.0, 5.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0]),
+        ];
+
+        compute_normals(&mut vertices, &[0, 1, 2]);
+
+        for vertex in &vertices[..3] {
+            assert_eq!(vertex.normal, [0.0, 0.0, 1.0]);
+        }
+        assert_eq!(vertices[3].normal, [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn indexes_after_the_last_triangle_are_ignored() {
+        let mut vertices = vec![
+            Vertex::new([0.0, 0.0, 0.0], [0.0; 3], [0.0; 3], [0.0, 0.0]),
+            Vertex::new([1.0, 0.0, 0.0], [0.0; 3], [0.0; 3], [1.0, 0.0]),
+            Vertex::new([0.0, 1.0, 0.0], [0.0; 3], [0.0; 3], [0.0, 1.0]),
+        ];
+
+        compute_normals(&mut vertices, &[0, 1, 2, 0, 1]);
+        compute_tangents(&mut vertices, &[0, 1, 2, 0, 1]);
+
+        for vertex in &vertices {
+            assert_eq!(vertex.normal, [0.0, 0.0, 1.0]);
+            assert_eq!(vertex.tangent, [1.0, 0.0, 0.0]);
+        }
+    }
+}
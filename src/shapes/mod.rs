@@ -1,63 +1,67 @@
 use std::iter::Map;
 use std::ops::Range;
-use glium::texture;
 use crate::{rotate, scale, translate};
 use crate::matrices::view_matrix;
 
 pub mod sphere;
+pub mod asteroid;
+pub mod asteroid_belt;
 pub mod cube;
+pub mod export;
 pub mod matrices;
+pub mod mesh;
+pub mod model;
 pub mod sky;
 pub mod ring_planet;
 pub mod triangle;
 pub mod star;
-
-/// Enum that decides the filling for a given shape, be it a RGB color or a texture.
-#[derive(Debug)]
-pub enum Filling {
-    Color([f32; 3]),
-    Texture(texture::SrgbTexture2d),
-}
-
-impl Filling {
-    pub fn get_color(&self) -> Option<[f32; 3]> {
-        if let Filling::Color(color) = self {
-            Some(*color)
-        } else {
-            None
-        }
-    }
-
-    pub fn get_texture(&self) -> Option<&texture::SrgbTexture2d> {
-        if let Filling::Texture(texture) = self {
-            Some(texture)
-        } else {
-            None
-        }
-    }
-}
+pub mod torus;
+pub mod cylinder;
+pub mod cone;
+pub mod plane;
+pub mod disc;
+pub mod capsule;
+pub mod programs;
+pub mod textures;
+pub mod noise;
+pub(crate) mod revolution;
+pub mod vectors;
+
+/// Direction the light comes from, for the shapes that are shaded.
+pub const LIGHT_DIRECTION: [f32; 3] = [0.8, -0.5, 1.0];
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vertex {
     position: [f32; 3],
+    normal: [f32; 3],
+    tangent: [f32; 3],
     tex_coords: [f32; 2]
 }
 
 impl Vertex {
-    pub fn new(p0: f32, p1: f32, p2: f32) -> Self {
-
-        /// Maps the (x, y, z) coordinates to the (u, v) coordinates of the sphere.
-        let u = - (0.5 + (p0.atan2(p2) / (std::f32::consts::PI * 2.0)));
-        let v = 0.5 + p1.asin() / std::f32::consts::PI;
+    /// Creates a vertex with the normal, tangent and (u, v) coordinates given by the shape generating it.
+    /// The tangent points towards increasing u.
+    pub fn new(position: [f32; 3], normal: [f32; 3], tangent: [f32; 3], tex_coords: [f32; 2]) -> Self {
+        Vertex {
+            position,
+            normal,
+            tangent,
+            tex_coords
+        }
+    }
 
+    /// Creates a vertex with only a position, for points and lines, which have no surface.
+    pub fn point(p0: f32, p1: f32, p2: f32) -> Self {
         Vertex {
             position: [p0, p1, p2],
-            tex_coords: [u, v]
+            normal: [0.0; 3],
+            tangent: [0.0; 3],
+            tex_coords: [0.0; 2]
         }
     }
 }
 
-implement_vertex!(Vertex, position, tex_coords);
+implement_vertex!(Vertex, position, normal, tangent, tex_coords);
 
 /// Function that normalizes a range [a, b] to a given range [c, d].
 pub fn map_range((from_start, from_end): (f32, f32), (to_start, to_end): (f32, f32), value: f32) -> f32 {
@@ -68,16 +72,6 @@ pub fn map_range((from_start, from_end): (f32, f32), (to_start, to_end): (f32, f
     to_value
 }
 
-/// Interface for moving drawable objects.
-pub trait DynDrawble {
-    fn draw(&self, target: &mut glium::Frame, params: &glium::DrawParameters, transform: Transform);
-}
-
-/// Interface for static drawable objects.
-pub trait StaticDrawble {
-    fn draw(&mut self, target: &mut glium::Frame, params: &glium::DrawParameters);
-}
-
 /// Struct that holds the transform parameters of a drawable object.
 #[derive(Clone)]
 pub struct Transform {
@@ -125,35 +119,54 @@ impl Transform {
     pub fn get_view(&self) -> [[f32; 4]; 4] {
         view_matrix(&self.view[0], &self.view[1], &self.view[2])
     }
+
+    /// Matrix placing the object in the world, in the same order as the shaders apply the transforms.
+    pub fn get_model(&self) -> [[f32; 4]; 4] {
+        matrices::multiply(
+            &self.get_rotation(),
+            &matrices::multiply(
+                &self.get_translation(),
+                &matrices::multiply(&self.get_scaling(), &self.get_self_rotation()),
+            ),
+        )
+    }
+
+    /// Direction from the scene towards the camera, used for specular lighting.
+    pub fn get_eye_direction(&self) -> [f32; 3] {
+        // The view matrix looks along its second parameter
+        vectors::normalize(vectors::scale(self.view[1], -1.0))
+    }
 }
 
-/// Macro that loads a texture from a file and returns a texture.
+/// Macro that loads a texture from a file and returns a texture, or the error that prevented it.
 #[macro_export]
 macro_rules! load_tex {
     ($display: ident, $path: expr, jpeg) => {
         {
-            let image = image::load(std::io::Cursor::new(&include_bytes!($path)),
-                            image::ImageFormat::Jpeg).unwrap().to_rgba8();
-
-            let image_dimensions = image.dimensions();
-            let image = glium::texture::RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions);
-
-            let texture = glium::texture::SrgbTexture2d::new(&$display, image).unwrap();
-
-            texture
+            image::load(std::io::Cursor::new(&include_bytes!($path)), image::ImageFormat::Jpeg)
+                .map_err(|e| $crate::error::Error::Texture(format!("Could not load texture {}: {e}", $path)))
+                .and_then(|image| {
+                    let image = image.to_rgba8();
+                    let image_dimensions = image.dimensions();
+                    let image = glium::texture::RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions);
+
+                    glium::texture::SrgbTexture2d::new(&$display, image)
+                        .map_err(|e| $crate::error::Error::Texture(format!("Could not create texture {}: {e}", $path)))
+                })
         }
     };
     ($display: ident, $path: expr, png) => {
         {
-            let image = image::load(std::io::Cursor::new(&include_bytes!($path)),
-                            image::ImageFormat::Png).unwrap().to_rgba8();
-
-            let image_dimensions = image.dimensions();
-            let image = glium::texture::RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions);
-
-            let texture = glium::texture::SrgbTexture2d::new(&$display, image).unwrap();
-
-            texture
+            image::load(std::io::Cursor::new(&include_bytes!($path)), image::ImageFormat::Png)
+                .map_err(|e| $crate::error::Error::Texture(format!("Could not load texture {}: {e}", $path)))
+                .and_then(|image| {
+                    let image = image.to_rgba8();
+                    let image_dimensions = image.dimensions();
+                    let image = glium::texture::RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions);
+
+                    glium::texture::SrgbTexture2d::new(&$display, image)
+                        .map_err(|e| $crate::error::Error::Texture(format!("Could not create texture {}: {e}", $path)))
+                })
         }
     };
 }
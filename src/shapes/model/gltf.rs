@@ -0,0 +1,203 @@
+use std::path::Path;
+use std::rc::Rc;
+
+use ::gltf::image::Format;
+use ::gltf::json::validation::Error as ValidationError;
+use ::gltf::json::Path as JsonPath;
+use glium::Display;
+
+use crate::error::{Error, Result};
+use crate::render::BlendMode;
+use crate::shapes::model::{Model, Node};
+use crate::shapes::mesh::{compute_normals, compute_tangents, Material, Mesh};
+use crate::shapes::textures::TextureManager;
+use crate::shapes::Vertex;
+
+impl Model {
+    /// Loads a glTF 2.0 file, either `.gltf` with its buffers and images or a binary `.glb`.
+    ///
+    /// The nodes of the default scene keep their hierarchy and transforms,
+    /// each triangle primitive becomes a mesh and the metallic-roughness materials
+    /// are mapped to `Material`. Images are added to the texture manager.
+    /// Normals and tangents are computed when the file has none.
+    ///
+    /// Only the base color texture of a material is loaded: its normal, occlusion, emissive
+    /// and metallic-roughness textures are ignored, and so are the primitives that are not
+    /// made of triangles, which are listed in the warnings of the model.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::model::Model;
+    /// use rt::shapes::textures::TextureManager;
+    /// let mut textures = TextureManager::new();
+    /// let station = Model::from_gltf(display, &mut textures, "models/station.gltf")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_gltf(
+        display: &Display,
+        textures: &mut TextureManager,
+        path: impl AsRef<Path>,
+    ) -> Result<Model> {
+        let path = path.as_ref();
+        let (document, buffers, images) = ::gltf::import(path)?;
+
+        let materials: Vec<Rc<Material>> = document.materials()
+            .map(|material| load_material(display, textures, path, &images, &material).map(Rc::new))
+            .collect::<Result<_>>()?;
+        let default_material = Rc::new(Material::default());
+
+        // Meshes of the model made from the primitives of each glTF mesh
+        let mut meshes = Vec::new();
+        let mut mesh_primitives = Vec::new();
+        let mut warnings = Vec::new();
+        for mesh in document.meshes() {
+            let mut primitives = Vec::new();
+            for primitive in mesh.primitives() {
+                if primitive.mode() != ::gltf::mesh::Mode::Triangles {
+                    warnings.push(format!("Skipped a primitive of {} that is not made of triangles", mesh.name().unwrap_or("a mesh")));
+                    continue;
+                }
+                let path = JsonPath::new().field("meshes").index(mesh.index()).field("primitives").index(primitive.index());
+                let (vertices, indices) = primitive_vertices(&primitive, &buffers, &path)?;
+                let material = primitive.material().index()
+                    .and_then(|index| materials.get(index))
+                    .unwrap_or(&default_material);
+
+                primitives.push(meshes.len());
+                meshes.push(Mesh::new(display, &vertices, &indices, Rc::clone(material))?);
+            }
+            mesh_primitives.push(primitives);
+        }
+
+        let nodes = document.default_scene()
+            .or_else(|| document.scenes().next())
+            .map(|scene| scene.nodes().map(|node| load_node(&node, &mesh_primitives)).collect())
+            .unwrap_or_default();
+
+        let mut model = Model::new(meshes, nodes);
+        model.warnings = warnings;
+        Ok(model)
+    }
+}
+
+fn load_node(node: &::gltf::Node, mesh_primitives: &[Vec<usize>]) -> Node {
+    Node {
+        name: node.name().unwrap_or_default().to_string(),
+        matrix: node.transform().matrix(),
+        meshes: node.mesh()
+            .map(|mesh| mesh_primitives[mesh.index()].clone())
+            .unwrap_or_default(),
+        children: node.children().map(|child| load_node(&child, mesh_primitives)).collect(),
+    }
+}
+
+fn load_material(
+    display: &Display,
+    textures: &mut TextureManager,
+    path: &Path,
+    images: &[::gltf::image::Data],
+    material: &::gltf::Material,
+) -> Result<Material> {
+    let pbr = material.pbr_metallic_roughness();
+
+    let texture = pbr.base_color_texture()
+        .map(|info| {
+            let index = info.texture().source().index();
+            let name = format!("{}#image{index}", path.display());
+            image_rgba(&images[index])
+                .ok_or_else(|| Error::Texture(format!("Unsupported pixel format in {name}")))
+                .and_then(|pixels| textures.load_rgba(display, name, pixels, (images[index].width, images[index].height)))
+        })
+        .transpose()?;
+
+    Ok(Material {
+        base_color: pbr.base_color_factor(),
+        texture,
+        metallic: pbr.metallic_factor(),
+        roughness: pbr.roughness_factor(),
+        emissive: material.emissive_factor(),
+        unlit: false,
+        blend: match material.alpha_mode() {
+            ::gltf::material::AlphaMode::Blend => Some(BlendMode::Alpha),
+            _ => None,
+        },
+    })
+}
+
+/// Converts the pixels of a glTF image to 8 bit RGBA.
+fn image_rgba(image: &::gltf::image::Data) -> Option<Vec<u8>> {
+    let pixels = &image.pixels;
+    match image.format {
+        Format::R8G8B8A8 => Some(pixels.clone()),
+        Format::R8G8B8 => Some(pixels.chunks(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect()),
+        Format::R8G8 => Some(pixels.chunks(2).flat_map(|p| [p[0], p[1], 0, 255]).collect()),
+        Format::R8 => Some(pixels.iter().flat_map(|&p| [p, p, p, 255]).collect()),
+        _ => None,
+    }
+}
+
+/// Reads the attributes of a triangle primitive into vertices.
+///
+/// Attributes with fewer or more values than there are positions, indexes past the last vertex,
+/// and indexes that don't make whole triangles are reported as invalid at their path in the file.
+fn primitive_vertices(primitive: &::gltf::Primitive, buffers: &[::gltf::buffer::Data], path: &JsonPath) -> Result<(Vec<Vertex>, Vec<u32>)> {
+    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+    let invalid = |attribute: &str| Error::Gltf(::gltf::Error::Validation(vec![
+        (path.field("attributes").key(attribute), ValidationError::Invalid),
+    ]));
+
+    let positions: Vec<[f32; 3]> = reader.read_positions().map(|p| p.collect()).unwrap_or_default();
+    let normals: Option<Vec<[f32; 3]>> = reader.read_normals().map(|n| n.collect());
+    let tangents: Option<Vec<[f32; 4]>> = reader.read_tangents().map(|t| t.collect());
+    let tex_coords: Option<Vec<[f32; 2]>> = reader.read_tex_coords(0).map(|t| t.into_f32().collect());
+
+    let counts = [
+        ("NORMAL", normals.as_ref().map(Vec::len)),
+        ("TANGENT", tangents.as_ref().map(Vec::len)),
+        ("TEXCOORD_0", tex_coords.as_ref().map(Vec::len)),
+    ];
+    for (attribute, count) in counts {
+        if count.is_some_and(|count| count != positions.len()) {
+            return Err(invalid(attribute));
+        }
+    }
+
+    let mut vertices: Vec<Vertex> = positions.iter()
+        .enumerate()
+        .map(|(i, &position)| {
+            let normal = normals.as_ref().map_or([0.0; 3], |normals| normals[i]);
+            let tangent = tangents.as_ref().map_or([0.0; 3], |tangents| {
+                let [x, y, z, _] = tangents[i];
+                [x, y, z]
+            });
+            // glTF puts v = 0 at the top of the image, our textures at the bottom
+            let [u, v] = tex_coords.as_ref().map_or([0.0; 2], |tex_coords| tex_coords[i]);
+            Vertex::new(position, normal, tangent, [u, 1.0 - v])
+        })
+        .collect();
+
+    let indices: Vec<u32> = reader.read_indices()
+        .map(|indices| indices.into_u32().collect())
+        .unwrap_or_else(|| (0..vertices.len() as u32).collect());
+    if indices.iter().any(|&index| index as usize >= vertices.len()) {
+        return Err(Error::Gltf(::gltf::Error::Validation(vec![
+            (path.field("indices"), ValidationError::IndexOutOfBounds),
+        ])));
+    }
+    if !indices.len().is_multiple_of(3) {
+        return Err(Error::Gltf(::gltf::Error::Validation(vec![
+            (path.field("indices"), ValidationError::Invalid),
+        ])));
+    }
+
+    if normals.is_none() {
+        compute_normals(&mut vertices, &indices);
+    }
+    if tangents.is_none() {
+        compute_tangents(&mut vertices, &indices);
+    }
+
+    Ok((vertices, indices))
+}
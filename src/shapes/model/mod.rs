@@ -0,0 +1,59 @@
+use crate::identity;
+use crate::error::Result;
+use crate::render::{Drawable, RenderContext, RenderTarget};
+use crate::shapes::Transform;
+use crate::shapes::matrices::multiply;
+use crate::shapes::mesh::Mesh;
+
+mod gltf;
+mod obj;
+
+/// Node of the hierarchy of a model, placing its meshes and children relative to its parent.
+pub struct Node {
+    pub name: String,
+    /// Column-major transform relative to the parent
+    pub matrix: [[f32; 4]; 4],
+    /// Indices of the meshes of the model drawn at this node
+    pub meshes: Vec<usize>,
+    pub children: Vec<Node>,
+}
+
+/// Object loaded from a model file: its meshes, placed by a hierarchy of nodes.
+pub struct Model {
+    meshes: Vec<Mesh>,
+    nodes: Vec<Node>,
+    warnings: Vec<String>,
+}
+
+impl Model {
+    pub fn new(meshes: Vec<Mesh>, nodes: Vec<Node>) -> Self {
+        Model { meshes, nodes, warnings: Vec::new() }
+    }
+
+    /// What the loader of the model file had to leave out, such as an unsupported primitive
+    /// or a missing texture, for the caller to report.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Draws the meshes of a node and of its children, with the matrices of its parents already applied.
+    fn draw_node(&self, node: &Node, parent: &[[f32; 4]; 4], target: &mut RenderTarget, context: &RenderContext, transform: &Transform) -> Result<()> {
+        let matrix = multiply(parent, &node.matrix);
+        for &mesh in &node.meshes {
+            self.meshes[mesh].draw_with_matrix(target, context, transform, matrix)?;
+        }
+        for child in &node.children {
+            self.draw_node(child, &matrix, target, context, transform)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drawable for Model {
+    fn draw(&self, target: &mut RenderTarget, context: &RenderContext, transform: &Transform) -> Result<()> {
+        for node in &self.nodes {
+            self.draw_node(node, &identity!(), target, context, transform)?;
+        }
+        Ok(())
+    }
+}
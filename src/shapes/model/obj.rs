@@ -0,0 +1,133 @@
+use std::path::Path;
+use std::rc::Rc;
+
+use glium::Display;
+
+use crate::identity;
+use crate::error::Result;
+use crate::render::BlendMode;
+use crate::shapes::model::{Model, Node};
+use crate::shapes::mesh::{compute_normals, compute_tangents, Material, Mesh};
+use crate::shapes::textures::TextureManager;
+use crate::shapes::Vertex;
+
+impl Model {
+    /// Loads a Wavefront OBJ file, with the materials of the MTL files it references.
+    ///
+    /// Faces are split into triangles. Normals are computed when the file has none.
+    /// Textures are looked up relative to the OBJ file, and materials whose texture
+    /// can't be loaded keep only their diffuse color, with the error in the warnings of the model,
+    /// like MTL files that can't be loaded.
+    /// All the meshes are placed at a single node.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::model::Model;
+    /// use rt::shapes::textures::TextureManager;
+    /// let mut textures = TextureManager::new();
+    /// let ship = Model::from_obj(display, &mut textures, "models/shuttle.obj")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_obj(
+        display: &Display,
+        textures: &mut TextureManager,
+        path: impl AsRef<Path>,
+    ) -> Result<Model> {
+        let path = path.as_ref();
+        let (models, materials) = tobj::load_obj(path, &tobj::GPU_LOAD_OPTIONS)?;
+
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut warnings = Vec::new();
+        let materials: Vec<Rc<Material>> = materials
+            .unwrap_or_else(|e| {
+                warnings.push(format!("Could not load the materials of {}: {e}", path.display()));
+                Vec::new()
+            })
+            .into_iter()
+            .map(|material| Rc::new(load_material(display, textures, directory, material, &mut warnings)))
+            .collect();
+        let default_material = Rc::new(Material::default());
+
+        let meshes = models.into_iter()
+            .filter(|model| !model.mesh.indices.is_empty())
+            .map(|model| {
+                let (vertices, indices) = mesh_vertices(&model.mesh);
+                let material = model.mesh.material_id
+                    .and_then(|id| materials.get(id))
+                    .unwrap_or(&default_material);
+                Mesh::new(display, &vertices, &indices, Rc::clone(material))
+            })
+            .collect::<Result<Vec<Mesh>>>()?;
+
+        let root = Node {
+            name: path.file_stem().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+            matrix: identity!(),
+            meshes: (0..meshes.len()).collect(),
+            children: Vec::new(),
+        };
+
+        let mut model = Model::new(meshes, vec![root]);
+        model.warnings = warnings;
+        Ok(model)
+    }
+}
+
+fn load_material(
+    display: &Display,
+    textures: &mut TextureManager,
+    directory: &Path,
+    material: tobj::Material,
+    warnings: &mut Vec<String>,
+) -> Material {
+    let texture = material.diffuse_texture
+        .filter(|name| !name.is_empty())
+        .and_then(|name| {
+            textures.load_file(display, &directory.join(name))
+                .map_err(|e| warnings.push(e.to_string()))
+                .ok()
+        });
+
+    // Textured materials often leave the diffuse color out
+    let [r, g, b] = material.diffuse.unwrap_or(if texture.is_some() { [1.0; 3] } else { [0.8; 3] });
+
+    let alpha = material.dissolve.unwrap_or(1.0);
+    Material {
+        base_color: [r, g, b, alpha],
+        texture,
+        // OBJ has no metallic workflow, the specular exponent is the closest to a roughness
+        roughness: material.shininess.map_or(0.8, |shininess| (2.0 / (shininess + 2.0)).sqrt()),
+        blend: (alpha < 1.0).then_some(BlendMode::Alpha),
+        ..Material::default()
+    }
+}
+
+/// Converts the flat attribute lists of an OBJ mesh into vertices.
+fn mesh_vertices(mesh: &tobj::Mesh) -> (Vec<Vertex>, Vec<u32>) {
+    let has_normals = !mesh.normals.is_empty();
+
+    let mut vertices: Vec<Vertex> = (0..mesh.positions.len() / 3)
+        .map(|i| {
+            let position = [mesh.positions[3 * i], mesh.positions[3 * i + 1], mesh.positions[3 * i + 2]];
+            let normal = if has_normals {
+                [mesh.normals[3 * i], mesh.normals[3 * i + 1], mesh.normals[3 * i + 2]]
+            } else {
+                [0.0; 3]
+            };
+            let tex_coords = if mesh.texcoords.is_empty() {
+                [0.0; 2]
+            } else {
+                [mesh.texcoords[2 * i], mesh.texcoords[2 * i + 1]]
+            };
+            Vertex::new(position, normal, [0.0; 3], tex_coords)
+        })
+        .collect();
+
+    if !has_normals {
+        compute_normals(&mut vertices, &mesh.indices);
+    }
+    compute_tangents(&mut vertices, &mesh.indices);
+
+    (vertices, mesh.indices.clone())
+}
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+
+use crate::shapes::mesh::{Material, MeshBuilder};
+use crate::shapes::plane::generate_vertices_and_indexes;
+use crate::shapes::Vertex;
+
+/// Helps to create a flat rectangle facing up, split in a grid.
+pub struct PlaneBuilder {
+    width: f32,
+    depth: f32,
+    columns: usize,
+    rows: usize,
+    color: [f32; 3],
+}
+
+impl PlaneBuilder {
+    /// Creates a new plane builder.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::mesh::MeshBuilder;
+    /// use rt::shapes::plane::PlaneBuilder;
+    /// let landing_pad = PlaneBuilder::new()
+    ///  .size(2.0, 1.0)
+    ///  .subdivisions(8, 4)
+    ///  .build(display)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new() -> Self {
+        PlaneBuilder {
+            width: 1.0,
+            depth: 1.0,
+            columns: 1,
+            rows: 1,
+            color: [0.8, 0.8, 0.8],
+        }
+    }
+
+    /// Sets the size of the plane along x and z.
+    pub fn size(mut self, width: f32, depth: f32) -> Self {
+        self.width = width;
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the number of cells of the grid along x and z.
+    pub fn subdivisions(mut self, columns: usize, rows: usize) -> Self {
+        self.columns = columns;
+        self.rows = rows;
+        self
+    }
+
+    /// Sets the color of the plane.
+    pub fn color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Default for PlaneBuilder {
+    fn default() -> Self {
+        PlaneBuilder::new()
+    }
+}
+
+impl MeshBuilder for PlaneBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        generate_vertices_and_indexes(self.width, self.depth, self.columns, self.rows)
+    }
+
+    fn material(&self) -> Material {
+        Material::color(self.color)
+    }
+}
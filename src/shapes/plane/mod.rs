@@ -0,0 +1,38 @@
+use crate::shapes::Vertex;
+
+mod builder;
+pub use self::builder::PlaneBuilder;
+
+/// Function that generates vertices and indexes for a flat grid on the y = 0 plane, facing up.
+///
+/// u goes along x and v along -z, so the texture reads the right way seen from above.
+pub(crate) fn generate_vertices_and_indexes(width: f32, depth: f32, columns: usize, rows: usize) -> (Vec<Vertex>, Vec<u32>) {
+    let columns = columns.max(1);
+    let rows = rows.max(1);
+
+    let mut vertices = Vec::with_capacity((columns + 1) * (rows + 1));
+    for row in 0..=rows {
+        let v = row as f32 / rows as f32;
+        for column in 0..=columns {
+            let u = column as f32 / columns as f32;
+            let position = [width * (u - 0.5), 0.0, depth * (0.5 - v)];
+            vertices.push(Vertex::new(position, [0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [u, v]));
+        }
+    }
+
+    let stride = columns as u32 + 1;
+    let mut indices = Vec::with_capacity(columns * rows * 6);
+    for row in 0..rows as u32 {
+        for column in 0..columns as u32 {
+            let bottom_left = row * stride + column;
+            let bottom_right = bottom_left + 1;
+            let top_left = bottom_left + stride;
+            let top_right = top_left + 1;
+
+            indices.extend_from_slice(&[bottom_left, bottom_right, top_right]);
+            indices.extend_from_slice(&[bottom_left, top_right, top_left]);
+        }
+    }
+
+    (vertices, indices)
+}
@@ -0,0 +1,34 @@
+// Lighting shared by the fragment shaders that are shaded
+
+uniform float metallic;
+uniform float roughness;
+uniform vec3 emissive;
+uniform vec3 light_direction;
+uniform vec3 eye_direction;
+
+const float PI = 3.14159265;
+const float AMBIENT = 0.15;
+
+// Metallic-roughness shading of a single directional light:
+// GGX distribution, Smith-Schlick geometry and Schlick fresnel
+vec3 shade(vec3 base_color, vec3 normal) {
+    vec3 n = normalize(normal);
+    vec3 l = normalize(light_direction);
+    vec3 v = normalize(eye_direction);
+    vec3 h = normalize(l + v);
+    float n_dot_l = max(dot(n, l), 0.0);
+    float n_dot_v = max(dot(n, v), 0.0001);
+    float n_dot_h = max(dot(n, h), 0.0);
+
+    float a = roughness * roughness;
+    float d = a * a / (PI * pow(n_dot_h * n_dot_h * (a * a - 1.0) + 1.0, 2.0));
+    float k = (roughness + 1.0) * (roughness + 1.0) / 8.0;
+    float g = n_dot_l / (n_dot_l * (1.0 - k) + k) * n_dot_v / (n_dot_v * (1.0 - k) + k);
+    vec3 f0 = mix(vec3(0.04), base_color, metallic);
+    vec3 f = f0 + (1.0 - f0) * pow(1.0 - max(dot(h, v), 0.0), 5.0);
+
+    vec3 specular = d * g * f / (4.0 * n_dot_v * max(n_dot_l, 0.0001));
+    vec3 diffuse = (1.0 - f) * (1.0 - metallic) * base_color / PI;
+    // The light is scaled by PI so a white matte surface facing it is fully lit
+    return AMBIENT * base_color + (diffuse + specular) * n_dot_l * PI + emissive;
+}
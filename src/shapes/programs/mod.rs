@@ -0,0 +1,270 @@
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant, SystemTime};
+
+use glium::{Display, Program};
+use glium::backend::Facade;
+
+use crate::error::Error;
+use crate::shader;
+use self::preprocessor::preprocess;
+
+mod preprocessor;
+
+/// Whether the shaders are read from `src/shapes` and recompiled when they change,
+/// enabled by the `hot-reload` feature.
+pub const HOT_RELOAD: bool = cfg!(feature = "hot-reload");
+
+/// How often the shader files are checked for changes
+const RELOAD_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Files every shader can `#include`, baked in like the shaders themselves
+const INCLUDES: [Shader; 2] = [
+    shader!("transform.glsl"),
+    shader!("lighting.glsl"),
+];
+
+thread_local! {
+    /// Caches of the displays created on this thread, OpenGL contexts being tied to their thread.
+    /// Held weakly, so a cache and its display go away with the last program using them.
+    static CACHES: RefCell<Vec<Weak<ProgramCache>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Creates a `Shader` from a file next to the module using it.
+///
+/// # Example
+/// The files are looked up next to the source file calling the macro,
+/// so this example is not compiled on its own.
+/// ```ignore
+/// let program = ProgramCache::for_display(display).get(shader!("mesh.vert"), shader!("mesh.frag"), &[])?;
+/// ```
+#[macro_export]
+macro_rules! shader {
+    ($file: literal) => {
+        $crate::shapes::programs::Shader::new(include_str!($file), file!(), $file)
+    };
+}
+
+/// Shader baked into the binary, which remembers its file so it can be reloaded.
+#[derive(Debug, Clone, Copy)]
+pub struct Shader {
+    source: &'static str,
+    /// Source file of the module the shader was included from, relative to the crate
+    module: &'static str,
+    /// Shader file, relative to the module
+    file: &'static str,
+}
+
+impl Shader {
+    pub const fn new(source: &'static str, module: &'static str, file: &'static str) -> Self {
+        Shader { source, module, file }
+    }
+
+    fn path(&self) -> PathBuf {
+        let module = Path::new(env!("CARGO_MANIFEST_DIR")).join(self.module);
+        module.parent().unwrap_or(&module).join(self.file)
+    }
+
+    /// Gives the source of the shader, read from its file when hot reloading.
+    fn load(&self) -> Result<String, String> {
+        if HOT_RELOAD {
+            fs::read_to_string(self.path()).map_err(|e| format!("Could not read {}: {e}", self.path().display()))
+        } else {
+            Ok(self.source.to_string())
+        }
+    }
+
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Shader with its includes expanded and the defines added, and the paths of the files it included.
+///
+/// An included file is looked up in the includes given by name, then, when hot reloading, next to the shader.
+/// The sources of the crate may not be on disk otherwise, so the other files must be given to `add_include`.
+fn expand(shader: &Shader, defines: &[(&str, &str)], includes: &[Shader]) -> Result<(String, Vec<PathBuf>), String> {
+    let registered = |file: &str| includes.iter().find(|include| include.file == file);
+    let beside = |file: &str| {
+        let path = shader.path();
+        path.parent().unwrap_or(&path).join(file)
+    };
+
+    let include = |file: &str| match registered(file) {
+        Some(include) => include.load(),
+        None if HOT_RELOAD => fs::read_to_string(beside(file))
+            .map_err(|_| format!("Could not find the included file {file}")),
+        None => Err(format!("Could not find the included file {file}, it must be given to add_include")),
+    };
+    let (source, included) = preprocess(&shader.load()?, defines, &include)
+        .map_err(|e| format!("{}: {e}", shader.path().display()))?;
+
+    let paths = included.iter()
+        .map(|file| registered(file).map_or_else(|| beside(file), Shader::path))
+        .collect();
+    Ok((source, paths))
+}
+
+/// Compiles a program, giving the preprocessing or compilation error as text,
+/// along with the paths of the files both shaders included.
+fn compile(display: &Display, vertex: &Shader, fragment: &Shader, defines: &[(&str, &str)], includes: &[Shader]) -> Result<(Program, Vec<PathBuf>), String> {
+    let (vertex, mut included) = expand(vertex, defines, includes)?;
+    let (fragment, fragment_included) = expand(fragment, defines, includes)?;
+    included.extend(fragment_included);
+    let program = Program::from_source(display, &vertex, &fragment, None).map_err(|e| e.to_string())?;
+    Ok((program, included))
+}
+
+/// Program compiled from a vertex and a fragment shader, replaced in place when they are reloaded.
+pub struct ShaderProgram {
+    program: RefCell<Program>,
+    vertex: Shader,
+    fragment: Shader,
+    defines: Vec<(&'static str, String)>,
+    /// Files the shaders included when the program was last compiled
+    included: RefCell<Vec<PathBuf>>,
+    /// Latest change of the shader files when the program was compiled
+    modified: Cell<Option<SystemTime>>,
+    /// Keeps the cache, and with it the display and the includes, while the program is in use
+    cache: Rc<ProgramCache>,
+}
+
+impl ShaderProgram {
+    /// Gives the program to draw with. The borrow must not be kept across frames.
+    pub fn get(&self) -> Ref<'_, Program> {
+        self.program.borrow()
+    }
+
+    /// Latest change of the shader files, including the files they included.
+    fn modified(&self) -> Option<SystemTime> {
+        [self.vertex.path(), self.fragment.path()].iter()
+            .chain(self.included.borrow().iter())
+            .filter_map(|path| modified(path))
+            .max()
+    }
+
+    /// Compiles the shaders again if their files changed, giving back the name of the program.
+    /// On errors the program compiled before is kept.
+    fn reload(&self, display: &Display) -> Option<crate::error::Result<String>> {
+        let modified = self.modified();
+        if modified <= self.modified.get() {
+            return None;
+        }
+        self.modified.set(modified);
+
+        let name = format!("{} + {}", self.vertex.path().display(), self.fragment.path().display());
+        let defines: Vec<(&str, &str)> = self.defines.iter().map(|(name, value)| (*name, value.as_str())).collect();
+        let result = compile(display, &self.vertex, &self.fragment, &defines, &self.cache.includes.borrow())
+            .map(|(program, included)| {
+                *self.program.borrow_mut() = program;
+                *self.included.borrow_mut() = included;
+                name.clone()
+            })
+            .map_err(|e| Error::Shader(format!("{name}, keeping the previous program:\n{e}")));
+        Some(result)
+    }
+}
+
+/// Shader sources and defines a program is compiled from.
+type ProgramKey = (&'static str, &'static str, Vec<(&'static str, String)>);
+
+/// Keeps the programs compiled for a display by their shader sources and defines,
+/// so each one is compiled only once and shared by every shape using it.
+///
+/// With the `hot-reload` feature the shaders are read from their files instead,
+/// and `reload` recompiles the ones whose files changed.
+///
+/// Shaders can `#include` the shared `transform.glsl` and `lighting.glsl`, the files given to
+/// `add_include`, and, when hot reloading, the files next to them.
+///
+/// The cache only keeps the programs while the shapes using them do,
+/// and is itself kept by its programs and whoever holds it.
+pub struct ProgramCache {
+    display: Display,
+    programs: RefCell<HashMap<ProgramKey, Weak<ShaderProgram>>>,
+    /// Files the shaders can include by name
+    includes: RefCell<Vec<Shader>>,
+    last_reload: Cell<Instant>,
+}
+
+impl ProgramCache {
+    /// Gives the cache of a display, shared by everything drawn on it.
+    pub fn for_display(display: &Display) -> Rc<ProgramCache> {
+        CACHES.with(|caches| {
+            let mut caches = caches.borrow_mut();
+            caches.retain(|cache| cache.strong_count() > 0);
+            let existing = caches.iter()
+                .filter_map(Weak::upgrade)
+                .find(|cache| Rc::ptr_eq(cache.display.get_context(), display.get_context()));
+            if let Some(cache) = existing {
+                return cache;
+            }
+
+            let cache = Rc::new(ProgramCache {
+                display: display.clone(),
+                programs: RefCell::new(HashMap::new()),
+                includes: RefCell::new(INCLUDES.to_vec()),
+                last_reload: Cell::new(Instant::now()),
+            });
+            caches.push(Rc::downgrade(&cache));
+            cache
+        })
+    }
+
+    /// Lets the shaders compiled after it `#include` a file by the name it was created with,
+    /// such as `shader!("noise.glsl")`, wherever the shaders are. It replaces an include of the same name.
+    pub fn add_include(&self, include: Shader) {
+        let mut includes = self.includes.borrow_mut();
+        includes.retain(|existing| existing.file != include.file);
+        includes.push(include);
+    }
+
+    /// Gives the program compiled from the shaders with the defines, compiling it the first time.
+    /// Each set of defines, such as `("HAS_TEXTURE", "1")`, gives a program of its own.
+    pub fn get(self: &Rc<Self>, vertex: Shader, fragment: Shader, defines: &[(&'static str, &str)]) -> crate::error::Result<Rc<ShaderProgram>> {
+        let owned_defines: Vec<(&'static str, String)> = defines.iter().map(|&(name, value)| (name, value.to_string())).collect();
+        let key = (vertex.source, fragment.source, owned_defines.clone());
+        if let Some(program) = self.programs.borrow().get(&key).and_then(Weak::upgrade) {
+            return Ok(program);
+        }
+
+        let (program, included) = compile(&self.display, &vertex, &fragment, defines, &self.includes.borrow())
+            .map_err(|e| Error::Shader(format!("{} + {}:\n{e}", vertex.path().display(), fragment.path().display())))?;
+        let program = ShaderProgram {
+            program: RefCell::new(program),
+            vertex,
+            fragment,
+            defines: owned_defines,
+            included: RefCell::new(included),
+            modified: Cell::new(None),
+            cache: Rc::clone(self),
+        };
+        program.modified.set(program.modified());
+
+        let program = Rc::new(program);
+        let mut programs = self.programs.borrow_mut();
+        programs.retain(|_, program| program.strong_count() > 0);
+        programs.insert(key, Rc::downgrade(&program));
+        Ok(program)
+    }
+
+    /// Recompiles the programs whose shader files changed, when hot reloading,
+    /// giving back the names of the programs reloaded or why they could not be.
+    /// Meant to be called every frame, the files are only checked every half second.
+    pub fn reload(&self) -> Vec<crate::error::Result<String>> {
+        if !HOT_RELOAD || self.last_reload.get().elapsed() < RELOAD_INTERVAL {
+            return Vec::new();
+        }
+        self.last_reload.set(Instant::now());
+
+        self.programs.borrow()
+            .values()
+            .filter_map(Weak::upgrade)
+            .filter_map(|program| program.reload(&self.display))
+            .collect()
+    }
+}
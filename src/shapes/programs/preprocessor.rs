@@ -0,0 +1,140 @@
+/// Expands the `#include "file"` lines of a shader and adds `#define` lines after its `#version`.
+///
+/// Each file is included only once, so files can include what they need without guards.
+/// `include` gives the source of a file, or why it can't.
+/// Gives back the names of the files included, in the order they were.
+///
+/// Conditionals such as `#ifdef` are left to the GLSL compiler, which sees the defines,
+/// so an `#include` inside one is an error: the file would count as included even when its block is left out.
+///
+/// `#line` directives keep the line numbers of compilation errors right. Their source string number
+/// is 0 for the shader, then 1 and up for the files included, in the order given back.
+pub(super) fn preprocess(
+    source: &str,
+    defines: &[(&str, &str)],
+    include: &dyn Fn(&str) -> Result<String, String>,
+) -> Result<(String, Vec<String>), String> {
+    let mut output = String::with_capacity(source.len());
+    let mut lines = source.lines().enumerate().peekable();
+
+    // #version must stay the first line
+    if let Some((_, version)) = lines.next_if(|(_, line)| line.trim_start().starts_with("#version")) {
+        output.push_str(version);
+        output.push('\n');
+    }
+    for (name, value) in defines {
+        output.push_str(&format!("#define {name} {value}\n"));
+    }
+    if let (false, Some(&(number, _))) = (defines.is_empty(), lines.peek()) {
+        output.push_str(&line_directive(number, 0));
+    }
+
+    let mut included = Vec::new();
+    expand(lines, 0, include, &mut included, &mut output)?;
+    Ok((output, included))
+}
+
+/// `#line` directive numbering the next line `index + 1`: in GLSL 3.30 the line after `#line n` is line `n + 1`.
+fn line_directive(index: usize, source_number: usize) -> String {
+    format!("#line {index} {source_number}\n")
+}
+
+/// Expands the lines of a file, `source_number` telling it apart in the `#line` directives.
+fn expand<'a>(
+    lines: impl Iterator<Item = (usize, &'a str)>,
+    source_number: usize,
+    include: &dyn Fn(&str) -> Result<String, String>,
+    included: &mut Vec<String>,
+    output: &mut String,
+) -> Result<(), String> {
+    // Conditional blocks the line is in
+    let mut depth = 0usize;
+    for (index, line) in lines {
+        let directive = line.trim();
+        if directive.starts_with("#if") {
+            depth += 1;
+        } else if directive.starts_with("#endif") {
+            depth = depth.saturating_sub(1);
+        }
+
+        let Some(file) = directive.strip_prefix("#include") else {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        };
+
+        let file = file.trim().trim_matches('"');
+        if depth > 0 {
+            let within = source_number.checked_sub(1).map_or(String::new(), |i| format!("{}, ", included[i]));
+            return Err(format!("{within}line {}: {file} is included inside a conditional block", index + 1));
+        }
+        if included.iter().any(|included| included == file) {
+            // An empty line, so the next ones keep their numbers
+            output.push('\n');
+            continue;
+        }
+        included.push(file.to_string());
+        let included_number = included.len();
+
+        let source = include(file)?;
+        output.push_str(&line_directive(0, included_number));
+        expand(source.lines().enumerate(), included_number, include, included, output)?;
+        output.push_str(&line_directive(index + 1, source_number));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_includes(file: &str) -> Result<String, String> {
+        Err(format!("Could not find the included file {file}"))
+    }
+
+    #[test]
+    fn defines_go_after_the_version() {
+        let (output, _) = preprocess("#version 330\nvoid main() {}", &[("HAS_TEXTURE", "1"), ("UNLIT", "1")], &no_includes).unwrap();
+        assert_eq!(output, "#version 330\n#define HAS_TEXTURE 1\n#define UNLIT 1\n#line 1 0\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn defines_go_first_without_a_version() {
+        let (output, _) = preprocess("void main() {}", &[("UNLIT", "1")], &no_includes).unwrap();
+        assert_eq!(output, "#define UNLIT 1\n#line 0 0\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn each_file_is_included_once() {
+        let include = |file: &str| match file {
+            "a.glsl" => Ok("#include \"b.glsl\"\nfloat a;".to_string()),
+            "b.glsl" => Ok("float b;".to_string()),
+            _ => no_includes(file),
+        };
+        let source = "#version 330\n#include \"a.glsl\"\n#include \"b.glsl\"\n#include \"a.glsl\"\nvoid main() {}";
+        let (output, included) = preprocess(source, &[], &include).unwrap();
+        assert_eq!(output, "#version 330\n#line 0 1\n#line 0 2\nfloat b;\n#line 1 1\nfloat a;\n#line 2 0\n\n\nvoid main() {}\n");
+        assert_eq!(included, ["a.glsl", "b.glsl"]);
+    }
+
+    #[test]
+    fn missing_includes_are_errors() {
+        let error = preprocess("#include \"missing.glsl\"", &[], &no_includes).unwrap_err();
+        assert!(error.contains("missing.glsl"));
+    }
+
+    #[test]
+    fn includes_inside_conditionals_are_errors() {
+        let include = |file: &str| match file {
+            "a.glsl" => Ok("float a;".to_string()),
+            "b.glsl" => Ok("#ifdef UNLIT\n#include \"a.glsl\"\n#endif".to_string()),
+            _ => no_includes(file),
+        };
+
+        let error = preprocess("#ifndef UNLIT\n#include \"a.glsl\"\n#endif", &[], &include).unwrap_err();
+        assert_eq!(error, "line 2: a.glsl is included inside a conditional block");
+        let error = preprocess("#include \"b.glsl\"", &[], &include).unwrap_err();
+        assert_eq!(error, "b.glsl, line 2: a.glsl is included inside a conditional block");
+        assert!(preprocess("#ifdef UNLIT\n#endif\n#include \"a.glsl\"", &[], &include).is_ok());
+    }
+}
@@ -0,0 +1,23 @@
+// Transform chain shared by the vertex shaders, see `Transform` in shapes/mod.rs
+
+uniform mat4 translation;
+uniform mat4 rotation;
+uniform mat4 scale;
+uniform mat4 self_rotation;
+uniform mat4 view;
+
+// Places a point of the shape in the view, operations occur from right to left
+vec4 place(vec3 position) {
+    return
+    view *
+    rotation *
+    translation *
+    scale *
+    self_rotation *
+    vec4(position, 1.0);
+}
+
+// Only rotations change the direction of a normal
+vec3 place_normal(vec3 normal) {
+    return mat3(rotation * self_rotation) * normal;
+}
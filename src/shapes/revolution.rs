@@ -0,0 +1,72 @@
+//! Surfaces made by turning a profile around the y axis, shared by the round primitives.
+
+use std::f32::consts::TAU;
+
+use crate::shapes::Vertex;
+
+/// Point of the profile of a surface of revolution, in a plane going through the y axis.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ProfilePoint {
+    /// Distance from the y axis, exactly 0 for points on the axis
+    pub radius: f32,
+    pub height: f32,
+    /// Normal as (away from the axis, along the axis)
+    pub normal: [f32; 2],
+    /// Texture coordinate along the profile
+    pub v: f32,
+}
+
+impl ProfilePoint {
+    pub fn new(radius: f32, height: f32, normal: [f32; 2], v: f32) -> Self {
+        ProfilePoint { radius, height, normal, v }
+    }
+}
+
+/// Turns a profile around the y axis in `segments` steps.
+///
+/// The profile must keep its normal on its right, e.g. go upwards along an outer wall,
+/// so the triangles are counter-clockwise seen from the side the normals point to.
+/// u goes around the axis starting from +z, with the first column repeated at the end with u = 1.
+/// Points on the axis get one vertex per column, in the middle of it, so they close the surface
+/// with a fan like the poles of the UV sphere.
+pub(crate) fn revolve(profile: &[ProfilePoint], segments: usize) -> (Vec<Vertex>, Vec<u32>) {
+    let segments = segments.max(3);
+    let on_axis = |point: &ProfilePoint| point.radius == 0.0;
+
+    let mut vertices = Vec::with_capacity(profile.len() * (segments + 1));
+    for point in profile {
+        let offset = if on_axis(point) { 0.5 } else { 0.0 };
+        for column in 0..=segments {
+            let u = (column as f32 + offset) / segments as f32;
+            let (sin, cos) = (u * TAU).sin_cos();
+
+            let position = [point.radius * sin, point.height, point.radius * cos];
+            let normal = [point.normal[0] * sin, point.normal[1], point.normal[0] * cos];
+            let tangent = [cos, 0.0, -sin];
+            vertices.push(Vertex::new(position, normal, tangent, [u, point.v]));
+        }
+    }
+
+    let row = segments as u32 + 1;
+    let mut indices = Vec::with_capacity(profile.len().saturating_sub(1) * segments * 6);
+    for (band, pair) in profile.windows(2).enumerate() {
+        for column in 0..segments as u32 {
+            let left = band as u32 * row + column;
+            let right = left + 1;
+            let next_left = left + row;
+            let next_right = next_left + 1;
+
+            match (on_axis(&pair[0]), on_axis(&pair[1])) {
+                (true, true) => (),
+                (true, false) => indices.extend_from_slice(&[left, next_right, next_left]),
+                (false, true) => indices.extend_from_slice(&[left, right, next_left]),
+                (false, false) => {
+                    indices.extend_from_slice(&[left, right, next_right]);
+                    indices.extend_from_slice(&[left, next_right, next_left]);
+                }
+            }
+        }
+    }
+
+    (vertices, indices)
+}
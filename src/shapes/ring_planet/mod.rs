@@ -1,62 +1,61 @@
-use std::f32::consts::PI;
-use glium::{Display, DrawParameters, Frame, Surface, VertexBuffer};
-use crate::{DynDrawble, shapes, StaticDrawble, Transform};
-use crate::shapes::sphere::{Sphere, SphereBuilder};
+use std::rc::Rc;
+
+use glium::index::PrimitiveType;
+use crate::Transform;
+use crate::error::{Error, Result};
+use crate::render::{Drawable, RenderContext, RenderTarget};
 use crate::shapes::Vertex;
-use crate::translate;
+use crate::shapes::export::{Exportable, MeshData};
+use crate::shapes::mesh::{Material, Mesh};
 
 pub struct RingPlanet {
-    planet: Sphere,
-    ring: VertexBuffer<Vertex>,
-    ring_program: glium::Program,
+    planet: Mesh,
+    ring: Mesh,
 }
 
 impl RingPlanet {
-    pub fn new(display: &glium::Display, ring_radius: f32, sphere: Sphere) -> RingPlanet {
-        assert!(sphere.radius() < ring_radius);
+    pub fn new(display: &glium::Display, ring_radius: f32, planet: Mesh) -> Result<RingPlanet> {
+        if planet.bounding_radius() >= ring_radius {
+            return Err(Error::InvalidShape(format!(
+                "ring radius {ring_radius} does not clear the planet radius {}",
+                planet.bounding_radius(),
+            )));
+        }
         let ring_points = (0..360)
             .map(|i| (i as f32).to_radians())
             .map(|angle| {
                 let x = angle.cos() * ring_radius;
                 let z = angle.sin() * ring_radius;
-                Vertex {
-                    position: [x, 0.0, z],
-                    tex_coords: [0.0, 0.0],
-                }
+                Vertex::point(x, 0.0, z)
             })
             .collect::<Vec<_>>();
-        let ring = VertexBuffer::new(display, &ring_points).unwrap();
-        let ring_program = glium::Program::from_source(display,
-            include_str!("../sphere/sphere.vert"),
-            include_str!("../sphere/sphere.frag"),
-            None).unwrap();
+        let ring_indices = (0..ring_points.len() as u32).collect::<Vec<_>>();
+        let ring = Mesh::with_primitive(
+            display,
+            &ring_points,
+            &ring_indices,
+            PrimitiveType::LineLoop,
+            Rc::new(Material::unlit([1.0, 1.0, 1.0])),
+        )?;
 
-        RingPlanet {
-            planet: sphere,
+        Ok(RingPlanet {
+            planet,
             ring,
-            ring_program,
-        }
+        })
     }
 }
 
-impl DynDrawble for RingPlanet {
-    fn draw(&self, target: &mut Frame, params: &DrawParameters, transform: Transform) {
-        let uniforms = uniform! {
-                translation: transform.get_translation(),
-                undo_translation: translate!(-transform.translation[0], -transform.translation[1], -transform.translation[2]),
-                scale: transform.get_scaling(),
-                rotation: transform.get_rotation(),
-                self_rotation: transform.get_self_rotation(),
-                color: [1.0f32, 1.0, 1.0],
-                view: transform.get_view(),
-            };
-        self.planet.draw(target, params, transform);
-        target.draw(
-            &self.ring,
-            glium::index::NoIndices(glium::index::PrimitiveType::LineLoop),
-            &self.ring_program,
-            &uniforms,
-            params
-        ).unwrap();
+impl Drawable for RingPlanet {
+    fn draw(&self, target: &mut RenderTarget, context: &RenderContext, transform: &Transform) -> Result<()> {
+        self.planet.draw(target, context, transform)?;
+        self.ring.draw(target, context, transform)
     }
-}
\ No newline at end of file
+}
+
+impl Exportable for RingPlanet {
+    fn mesh_data(&self) -> Vec<MeshData> {
+        let mut data = self.planet.mesh_data();
+        data.extend(self.ring.mesh_data());
+        data
+    }
+}
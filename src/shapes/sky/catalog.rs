@@ -0,0 +1,185 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+/// Brightest stars of the sky, bundled with the crate.
+const BRIGHT_STARS: &str = include_str!("stars.csv");
+
+/// Star of a catalog, placed on the sky by its equatorial coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogStar {
+    pub name: String,
+    /// Right ascension, in hours
+    pub right_ascension: f32,
+    /// Declination, in degrees
+    pub declination: f32,
+    /// Apparent visual magnitude, lower for brighter stars
+    pub magnitude: f32,
+    /// B-V color index, from about -0.3 for blue stars to 2 for red ones
+    pub color_index: f32,
+}
+
+impl CatalogStar {
+    /// Direction of the star on the unit sphere, with +Y towards the north celestial pole
+    /// and +X towards the right ascension 0h.
+    pub fn direction(&self) -> [f32; 3] {
+        let ascension = (self.right_ascension * 15.0).to_radians();
+        let declination = self.declination.to_radians();
+        [
+            declination.cos() * ascension.cos(),
+            declination.sin(),
+            -declination.cos() * ascension.sin(),
+        ]
+    }
+
+    /// Size of the star on the screen, in pixels, larger for brighter stars.
+    pub fn size(&self) -> f32 {
+        (5.0 - 0.8 * self.magnitude).clamp(1.0, 7.0)
+    }
+
+    /// Color of the star from its temperature, as bright as the star
+    /// so the brightest ones glow through the bloom.
+    pub fn color(&self) -> [f32; 3] {
+        // Flux relative to a first magnitude star
+        let brightness = 10f32.powf(-0.4 * (self.magnitude - 1.0)).clamp(0.15, 3.0);
+        temperature_color(self.temperature()).map(|channel| channel * brightness)
+    }
+
+    /// Temperature of the surface of the star in kelvin, from its color index (Ballesteros, 2012).
+    pub fn temperature(&self) -> f32 {
+        let index = self.color_index;
+        4600.0 * (1.0 / (0.92 * index + 1.7) + 1.0 / (0.92 * index + 0.62))
+    }
+}
+
+/// Stars read from a CSV file, such as a subset of the HYG database.
+///
+/// The first line names the columns: `name` (or `proper`), `ra` in hours, `dec` in degrees,
+/// `mag` and `ci`, the B-V color index. Other columns are skipped, and so are lines starting with `#`.
+///
+/// ```
+/// let catalog = rt::shapes::sky::StarCatalog::parse("name,ra,dec,mag,ci\nVega,18.616,38.784,0.03,0.00").unwrap();
+/// let vega = catalog.find("Vega").unwrap();
+/// assert!(vega.temperature() > 9000.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StarCatalog {
+    pub stars: Vec<CatalogStar>,
+}
+
+impl StarCatalog {
+    /// The bright stars bundled with the crate, down to about magnitude 4.
+    ///
+    /// ```
+    /// let catalog = rt::shapes::sky::StarCatalog::bright_stars().unwrap();
+    /// assert_eq!(catalog.find("Sirius").map(|star| star.magnitude), Some(-1.46));
+    /// ```
+    pub fn bright_stars() -> Result<Self> {
+        StarCatalog::parse(BRIGHT_STARS)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .map_err(|e| Error::Catalog(format!("Could not read {}: {e}", path.display())))?;
+        StarCatalog::parse(&source)
+    }
+
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut lines = source.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+
+        let (_, header) = lines.next().ok_or_else(|| Error::Catalog("The catalog is empty".to_string()))?;
+        let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+        let column = |names: &[&str]| {
+            columns.iter()
+                .position(|column| names.contains(column))
+                .ok_or_else(|| Error::Catalog(format!("The catalog has no {} column", names[0])))
+        };
+        let name = column(&["name", "proper"])?;
+        let right_ascension = column(&["ra"])?;
+        let declination = column(&["dec"])?;
+        let magnitude = column(&["mag"])?;
+        let color_index = column(&["ci"])?;
+
+        let stars = lines
+            .map(|(number, line)| {
+                let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+                let number = number + 1;
+                let number_at = |column: usize, default: Option<f32>| {
+                    match fields.get(column).copied().unwrap_or("") {
+                        // Some stars of the HYG database have no color index
+                        "" => default.ok_or_else(|| Error::Catalog(format!("Line {number} has no {}", columns[column]))),
+                        field => field.parse().map_err(|_| Error::Catalog(format!("Line {number} has {field} as {}", columns[column]))),
+                    }
+                };
+                Ok(CatalogStar {
+                    name: fields.get(name).copied().unwrap_or("").to_string(),
+                    right_ascension: number_at(right_ascension, None)?,
+                    declination: number_at(declination, None)?,
+                    magnitude: number_at(magnitude, None)?,
+                    // About the color of the sun
+                    color_index: number_at(color_index, Some(0.65))?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(StarCatalog { stars })
+    }
+
+    /// Star of the catalog with that name, if any.
+    pub fn find(&self, name: &str) -> Option<&CatalogStar> {
+        self.stars.iter().find(|star| star.name == name)
+    }
+}
+
+/// Color of a black body at a temperature in kelvin, brightest channel at 1
+/// (after the fit of Tanner Helland).
+fn temperature_color(kelvin: f32) -> [f32; 3] {
+    let t = kelvin / 100.0;
+    let red = if t <= 66.0 { 255.0 } else { 329.7 * (t - 60.0).powf(-0.1332) };
+    let green = if t <= 66.0 { 99.47 * t.ln() - 161.12 } else { 288.12 * (t - 60.0).powf(-0.0755) };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.52 * (t - 10.0).ln() - 305.04
+    };
+
+    let color = [red, green, blue].map(|channel: f32| channel.clamp(0.0, 255.0));
+    let brightest = color[0].max(color[1]).max(color[2]);
+    color.map(|channel| channel / brightest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(result: Result<StarCatalog>) -> String {
+        match result {
+            Err(Error::Catalog(message)) => message,
+            Err(e) => panic!("unexpected error {e}"),
+            Ok(_) => panic!("the catalog should not parse"),
+        }
+    }
+
+    #[test]
+    fn missing_column() {
+        let message = message(StarCatalog::parse("name,ra,dec,ci\nVega,18.616,38.784,0.00"));
+        assert!(message.contains("mag"), "{message}");
+    }
+
+    #[test]
+    fn empty_color_index_is_the_sun() {
+        let catalog = StarCatalog::parse("proper,ra,dec,mag,ci\nVega,18.616,38.784,0.03,").unwrap();
+        assert_eq!(catalog.find("Vega").map(|star| star.color_index), Some(0.65));
+    }
+
+    #[test]
+    fn bad_number() {
+        let message = message(StarCatalog::parse("name,ra,dec,mag,ci\n# Comment\nVega,18.616,north,0.03,0.00"));
+        assert_eq!(message, "Line 3 has north as dec");
+    }
+}
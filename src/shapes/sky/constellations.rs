@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+use glium::index::{NoIndices, PrimitiveType};
+use glium::texture::Texture2d;
+use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter};
+use glium::{Display, Surface, VertexBuffer};
+
+use super::font::{font_texture, glyph_range, GLYPH_SIZE};
+use super::{sky_rotation, StarCatalog};
+use crate::error::{Error, Result};
+use crate::render::{Drawable, RenderContext, RenderTarget};
+use crate::shapes::programs::{ProgramCache, ShaderProgram};
+use crate::shapes::{Transform, Vertex};
+use crate::{identity, shader};
+
+/// Stick figures of the brightest constellations, bundled with the crate.
+const CONSTELLATIONS: &str = include_str!("constellations.txt");
+
+/// Faint, so the lines stay behind the stars
+const LINE_COLOR: [f32; 3] = [0.12, 0.18, 0.35];
+const LABEL_COLOR: [f32; 3] = [0.3, 0.4, 0.7];
+/// Pixels of the screen per texel of the font
+const LABEL_SCALE: f32 = 2.0;
+/// Pixels between the center of a constellation and the top of its name
+const LABEL_OFFSET: f32 = 8.0;
+
+/// Constellation drawn as segments between the directions of its stars.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constellation {
+    pub name: String,
+    pub segments: Vec<[[f32; 3]; 2]>,
+}
+
+impl Constellation {
+    /// Direction of the middle of the constellation, where its name is written.
+    pub fn center(&self) -> [f32; 3] {
+        let sum = self.segments.iter()
+            .flatten()
+            .fold([0.0; 3], |sum, point| [sum[0] + point[0], sum[1] + point[1], sum[2] + point[2]]);
+        let length = sum.iter().map(|c| c * c).sum::<f32>().sqrt().max(f32::EPSILON);
+        sum.map(|c| c / length)
+    }
+
+    /// The constellations bundled with the crate, with the stars of a catalog having their names,
+    /// and the figures left out because a star of theirs is not in the catalog.
+    ///
+    /// ```
+    /// use rt::shapes::sky::{Constellation, StarCatalog};
+    ///
+    /// let (constellations, skipped) = Constellation::bundled(&StarCatalog::bright_stars().unwrap()).unwrap();
+    /// assert!(skipped.is_empty(), "{skipped:?}");
+    /// let orion = constellations.iter().find(|c| c.name == "Orion").unwrap();
+    /// assert_eq!(orion.segments.len(), 10);
+    /// ```
+    pub fn bundled(catalog: &StarCatalog) -> Result<(Vec<Self>, Vec<String>)> {
+        Constellation::parse(CONSTELLATIONS, catalog)
+    }
+
+    pub fn load(path: impl AsRef<Path>, catalog: &StarCatalog) -> Result<(Vec<Self>, Vec<String>)> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .map_err(|e| Error::Catalog(format!("Could not read {}: {e}", path.display())))?;
+        Constellation::parse(&source, catalog)
+    }
+
+    /// Reads lines such as `Lyra = Vega, Sheliak, Sulafat`: the name of the constellation,
+    /// then a chain of stars of the catalog separated by commas, which can't be in their names.
+    /// A figure of several chains takes a line for each. Lines starting with `#` are comments.
+    ///
+    /// Gives back the constellations whose stars are all in the catalog,
+    /// and why the others were left out.
+    pub fn parse(source: &str, catalog: &StarCatalog) -> Result<(Vec<Self>, Vec<String>)> {
+        let mut constellations: Vec<Constellation> = Vec::new();
+        let mut skipped: Vec<(String, String)> = Vec::new();
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, chain) = line.split_once('=')
+                .ok_or_else(|| Error::Catalog(format!("line {} should be name = stars: {line}", number + 1)))?;
+            let name = name.trim();
+            if skipped.iter().any(|(skipped, _)| skipped == name) {
+                continue;
+            }
+
+            let directions = chain.split(',')
+                .map(str::trim)
+                .map(|star| catalog.find(star)
+                    .map(|star| star.direction())
+                    .ok_or_else(|| format!("Skipped {name}: {star} is not in the catalog")))
+                .collect::<std::result::Result<Vec<_>, String>>();
+            let directions = match directions {
+                Ok(directions) => directions,
+                Err(reason) => {
+                    constellations.retain(|constellation| constellation.name != name);
+                    skipped.push((name.to_string(), reason));
+                    continue;
+                }
+            };
+
+            let segments = directions.windows(2).map(|pair| [pair[0], pair[1]]);
+            match constellations.iter_mut().find(|constellation| constellation.name == name) {
+                Some(constellation) => constellation.segments.extend(segments),
+                None => constellations.push(Constellation { name: name.to_string(), segments: segments.collect() }),
+            }
+        }
+        Ok((constellations, skipped.into_iter().map(|(_, reason)| reason).collect()))
+    }
+}
+
+/// Corner of a glyph of a label, placed in pixels around the center of its constellation.
+#[derive(Debug, Clone, Copy)]
+struct LabelVertex {
+    /// Direction of the center of the constellation
+    anchor: [f32; 3],
+    /// Offset from the anchor, in pixels
+    offset: [f32; 2],
+    tex_coords: [f32; 2],
+}
+
+implement_vertex!(LabelVertex, anchor, offset, tex_coords);
+
+/// Lines between the stars of constellations, with their names written under them.
+/// Like the sky, it turns with the camera but doesn't move with it.
+pub struct Constellations {
+    lines: VertexBuffer<Vertex>,
+    line_program: Rc<ShaderProgram>,
+    labels: VertexBuffer<LabelVertex>,
+    label_program: Rc<ShaderProgram>,
+    font: Texture2d,
+}
+
+impl Constellations {
+    pub fn new(display: &Display, constellations: &[Constellation]) -> Result<Self> {
+        let lines: Vec<Vertex> = constellations.iter()
+            .flat_map(|constellation| constellation.segments.iter().flatten())
+            .map(|&[x, y, z]| Vertex::point(x, y, z))
+            .collect();
+
+        let labels: Vec<LabelVertex> = constellations.iter()
+            .flat_map(|constellation| label_vertices(&constellation.name, constellation.center()))
+            .collect();
+
+        let programs = ProgramCache::for_display(display);
+        Ok(Constellations {
+            lines: VertexBuffer::new(display, &lines)?,
+            line_program: programs.get(shader!("sky.vert"), shader!("sky.frag"), &[])?,
+            labels: VertexBuffer::new(display, &labels)?,
+            label_program: programs.get(shader!("label.vert"), shader!("label.frag"), &[])?,
+            font: font_texture(display)?,
+        })
+    }
+}
+
+/// Two triangles per character of the name, centered under the anchor.
+fn label_vertices(name: &str, anchor: [f32; 3]) -> Vec<LabelVertex> {
+    let (glyph_width, glyph_height) = (GLYPH_SIZE.0 as f32 * LABEL_SCALE, GLYPH_SIZE.1 as f32 * LABEL_SCALE);
+    // A column of texels between the glyphs
+    let advance = glyph_width + LABEL_SCALE;
+    let width = name.chars().count() as f32 * advance - LABEL_SCALE;
+
+    let (top, bottom) = (-LABEL_OFFSET, -LABEL_OFFSET - glyph_height);
+    name.chars()
+        .enumerate()
+        .flat_map(|(index, character)| {
+            let left = index as f32 * advance - width / 2.0;
+            let right = left + glyph_width;
+            let (u_left, u_right) = glyph_range(character);
+            let corner = |x, y, u, v| LabelVertex { anchor, offset: [x, y], tex_coords: [u, v] };
+            [
+                corner(left, bottom, u_left, 0.0),
+                corner(right, bottom, u_right, 0.0),
+                corner(right, top, u_right, 1.0),
+                corner(left, bottom, u_left, 0.0),
+                corner(right, top, u_right, 1.0),
+                corner(left, top, u_left, 1.0),
+            ]
+        })
+        .collect()
+}
+
+/// Drawn in the pass of the sky, after it, so the transform is not used.
+impl Drawable for Constellations {
+    fn draw(&self, target: &mut RenderTarget, context: &RenderContext, _transform: &Transform) -> Result<()> {
+        let rotation = sky_rotation(context);
+
+        target.draw(
+            &self.lines,
+            NoIndices(PrimitiveType::LinesList),
+            &self.line_program.get(),
+            &uniform! {
+                translation: identity!(),
+                view: rotation,
+                projection: context.projection,
+                color: LINE_COLOR,
+            },
+            &context.params,
+        )?;
+
+        let (width, height) = target.get_dimensions();
+        target.draw(
+            &self.labels,
+            NoIndices(PrimitiveType::TrianglesList),
+            &self.label_program.get(),
+            &uniform! {
+                view: rotation,
+                projection: context.projection,
+                // Size of a pixel in normalized device coordinates
+                pixel_size: [2.0 / width as f32, 2.0 / height as f32],
+                font: self.font.sampled()
+                    .magnify_filter(MagnifySamplerFilter::Nearest)
+                    .minify_filter(MinifySamplerFilter::Nearest),
+                color: LABEL_COLOR,
+            },
+            &context.params,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn figures_with_a_missing_star_are_skipped() {
+        let catalog = StarCatalog::parse("name,ra,dec,mag,ci\nAl-Nair,22.137,-46.961,1.74,-0.07\nFomalhaut,22.961,-29.622,1.16,0.09\nVega,18.616,38.784,0.03,0.00").unwrap();
+        let source = "Grus = Al-Nair, Fomalhaut\nLyra = Vega, Sheliak\nLyra = Vega, Vega";
+
+        let (constellations, skipped) = Constellation::parse(source, &catalog).unwrap();
+
+        assert_eq!(constellations.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), ["Grus"]);
+        assert_eq!(constellations[0].segments.len(), 1);
+        assert_eq!(skipped, ["Skipped Lyra: Sheliak is not in the catalog"]);
+    }
+}
@@ -0,0 +1,68 @@
+use glium::texture::{RawImage2d, Texture2d};
+use glium::Display;
+
+use crate::error::{Error, Result};
+
+/// Width and height of a glyph, in texels.
+pub(super) const GLYPH_SIZE: (u32, u32) = (5, 7);
+
+/// Characters of the font, in the order of their glyphs in the texture.
+const CHARACTERS: &str = " ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Rows of each glyph from the top, the leftmost pixel in the highest of the five bits.
+const GLYPHS: [[u8; 7]; 27] = [
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000],
+    [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+    [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+    [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+    [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+    [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+    [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+    [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+    [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+    [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+    [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+    [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+    [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+    [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+    [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100],
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+];
+
+/// Texture of the glyphs side by side, white where the glyph is drawn.
+pub(super) fn font_texture(display: &Display) -> Result<Texture2d> {
+    let (width, height) = GLYPH_SIZE;
+    let texture_width = width * GLYPHS.len() as u32;
+    let mut pixels = Vec::with_capacity((texture_width * height * 4) as usize);
+    for row in 0..height as usize {
+        for glyph in &GLYPHS {
+            for column in (0..width).rev() {
+                let value = if glyph[row] >> column & 1 == 1 { 255 } else { 0 };
+                pixels.extend([value; 4]);
+            }
+        }
+    }
+
+    // Flipped, so v = 0 is the bottom of the glyphs
+    let image = RawImage2d::from_raw_rgba_reversed(&pixels, (texture_width, height));
+    Texture2d::new(display, image).map_err(|e| Error::Texture(format!("Could not create the font: {e}")))
+}
+
+/// Horizontal range of the glyph of a character in the font texture,
+/// the glyph of a space for the characters the font doesn't have.
+pub(super) fn glyph_range(character: char) -> (f32, f32) {
+    let index = CHARACTERS.find(character.to_ascii_uppercase()).unwrap_or(0);
+    let glyph_width = 1.0 / GLYPHS.len() as f32;
+    (index as f32 * glyph_width, (index + 1) as f32 * glyph_width)
+}
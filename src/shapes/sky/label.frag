@@ -1,12 +1,15 @@
 #version 330
 
+in vec2 v_tex_coords;
+
+uniform sampler2D font;
 uniform vec3 color;
-uniform sampler2D tex;
 
-in vec2 v_tex_coords;
 out vec4 fragColor;
 
 void main() {
-
+    if (texture(font, v_tex_coords).r < 0.5) {
+        discard;
+    }
     fragColor = vec4(color, 1.0);
 }
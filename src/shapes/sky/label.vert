@@ -0,0 +1,20 @@
+#version 330
+
+in vec3 anchor;
+in vec2 offset;
+in vec2 tex_coords;
+
+uniform mat4 view;
+uniform mat4 projection;
+uniform vec2 pixel_size;
+
+out vec2 v_tex_coords;
+
+void main() {
+    // At the far plane, behind everything else
+    vec4 position = (projection * view * vec4(anchor, 1.0)).xyww;
+    // Moved in pixels, so the labels keep their size on the screen
+    position.xy += offset * pixel_size * position.w;
+    gl_Position = position;
+    v_tex_coords = tex_coords;
+}
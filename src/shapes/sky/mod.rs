@@ -1,72 +1,124 @@
-use std::iter::{Cycle, Map};
-use std::ops::Range;
-use glium::{Display, DrawParameters, Frame, Program, Surface, VertexBuffer};
+use glium::{Display, DrawParameters, Surface, VertexBuffer};
 use glium::index::NoIndices;
 use rand::prelude::ThreadRng;
 use rand::Rng;
-use crate::shapes::{map_range, StaticDrawble, Vertex};
-use crate::{identity, translate};
+use std::rc::Rc;
+use crate::render::{BlendMode, Drawable, RenderContext, RenderTarget};
+use crate::shapes::{map_range, Transform, Vertex};
+use crate::shapes::programs::{ProgramCache, ShaderProgram};
+use crate::{identity, shader, translate};
+use crate::error::Result;
+
+pub use self::catalog::{CatalogStar, StarCatalog};
+pub use self::constellations::{Constellation, Constellations};
+pub use self::panorama::Panorama;
+
+mod catalog;
+mod constellations;
+mod font;
+mod panorama;
+
+/// Star of the sky, drawn as a point sprite fading out from its center.
+#[derive(Debug, Clone, Copy)]
+pub struct StarVertex {
+    /// Direction of the star, on the unit sphere
+    position: [f32; 3],
+    /// Size of the point, in pixels
+    size: f32,
+    color: [f32; 3],
+}
+
+implement_vertex!(StarVertex, position, size, color);
+
+impl From<&CatalogStar> for StarVertex {
+    fn from(star: &CatalogStar) -> Self {
+        StarVertex {
+            position: star.direction(),
+            size: star.size(),
+            color: star.color(),
+        }
+    }
+}
 
+/// Stars of a catalog on a sphere around the camera, turning with it but never getting closer,
+/// over an optional panorama, with shooting stars crossing the screen.
 pub struct Sky {
-    pub vertices: VertexBuffer<Vertex>,
+    pub vertices: VertexBuffer<StarVertex>,
     pub indices: NoIndices,
-    pub program: Program,
+    pub star_program: Rc<ShaderProgram>,
+    /// Program of the shooting stars
+    pub program: Rc<ShaderProgram>,
     pub shooting_stars: VertexBuffer<Vertex>,
     pub shooting_indices: NoIndices,
-    pub shooting_frame: Cycle<Range<i32>>,
+    pub panorama: Option<Panorama>,
+    /// How much the brightness of the stars changes as they twinkle, 0 for steady stars
+    pub twinkle: f32,
+    /// Speed of the twinkle, in radians per second
+    pub twinkle_speed: f32,
 }
 
+const TWINKLE: f32 = 0.15;
+const TWINKLE_SPEED: f32 = 3.0;
+
+/// Color of the shooting stars, bright enough to glow through the bloom
+const SHOOTING_STAR_COLOR: [f32; 3] = [4.0, 3.8, 3.4];
+
 const ANIMATION_FRAMES: i32 = 480;
+/// Animation frames shown per second
+const ANIMATION_SPEED: f32 = 120.0;
 
 impl Sky {
-    pub fn new(display: &Display) -> Self {
-        Sky::with_count(display, 100, 7)
+    /// Sky of the bright stars bundled with the crate.
+    pub fn new(display: &Display) -> Result<Self> {
+        Sky::with_catalog(display, &StarCatalog::bright_stars()?, 7)
     }
 
-    pub fn with_count(display: &Display, star_count: u32, shooting_star_count: u32) -> Self {
-        let stars = Self::gen_vertices(star_count);
+    pub fn with_catalog(display: &Display, catalog: &StarCatalog, shooting_star_count: u32) -> Result<Self> {
+        let stars: Vec<StarVertex> = catalog.stars.iter().map(StarVertex::from).collect();
 
-        let vertices = VertexBuffer::new(display, &stars).unwrap();
+        let vertices = VertexBuffer::new(display, &stars)?;
         let indices = glium::index::NoIndices(glium::index::PrimitiveType::Points);
 
-        let program = glium::Program::from_source(display,
-                                                  include_str!("sky.vert"),
-                                                  include_str!("sky.frag"),
-                                                  None).unwrap();
+        let programs = ProgramCache::for_display(display);
+        let star_program = programs.get(shader!("stars.vert"), shader!("stars.frag"), &[])?;
+        let program = programs.get(shader!("sky.vert"), shader!("sky.frag"), &[])?;
 
         let shooting_stars = Self::gen_shooting_stars(shooting_star_count);
-        let shooting_stars = VertexBuffer::new(display, &shooting_stars).unwrap();
+        let shooting_stars = VertexBuffer::new(display, &shooting_stars)?;
         let shooting_indices = glium::index::NoIndices(glium::index::PrimitiveType::LinesList);
 
-        Sky {
+        Ok(Sky {
             vertices,
             indices,
+            star_program,
             program,
             shooting_stars,
             shooting_indices,
-            shooting_frame: (0..ANIMATION_FRAMES).cycle(),
-        }
+            panorama: None,
+            twinkle: TWINKLE,
+            twinkle_speed: TWINKLE_SPEED,
+        })
     }
 
-    fn gen_vertices(star_count: u32) -> Vec<Vertex> {
-        let mut rng = rand::thread_rng();
-        let stars = (0..star_count)
-            .map(|_| {
-                let x = rng.gen_range(-100..100i32) as f32 / 100.0;
-                let y = rng.gen_range(-100..100i32) as f32 / 100.0;
-                [x, y]
-            })
-            .map(|[x, y]| Vertex::new(x, y, -1.0))
-            .collect::<Vec<_>>();
-        stars
+    /// Draws a panorama behind the stars.
+    pub fn with_panorama(mut self, panorama: Panorama) -> Self {
+        self.panorama = Some(panorama);
+        self
+    }
+
+    /// Changes how much and how fast the stars twinkle.
+    pub fn with_twinkle(mut self, twinkle: f32, twinkle_speed: f32) -> Self {
+        self.twinkle = twinkle;
+        self.twinkle_speed = twinkle_speed;
+        self
     }
 
     fn gen_shooting_stars(shooting_star_count: u32) -> Vec<Vertex> {
         let mut rng = rand::thread_rng();
         let shooting_stars = Self::gen_rand_points_iter(shooting_star_count, rng)
             .map(|[x, y]| [
-                    Vertex::new(x, y, -1.0)
-                ,   Vertex::new(x - 0.5, y - 0.5, -1.0)
+                    Vertex::point(x, y, -1.0)
+                ,   Vertex::point(x - 0.5, y - 0.5, -1.0)
                 ]
             )
             .flatten()
@@ -84,48 +136,65 @@ impl Sky {
     }
 }
 
-impl StaticDrawble for Sky {
-    fn draw(&mut self, target: &mut Frame, params: &DrawParameters) {
-        let params = DrawParameters {
-            depth: glium::Depth {
-                range: (0.9, 1.0),
+/// The sky stays behind the scene whatever the camera, so the transform is not used.
+/// It turns with the camera but doesn't move with it, being infinitely far.
+/// It is drawn in its own pass, before the objects of the scene.
+impl Drawable for Sky {
+    fn draw(&self, target: &mut RenderTarget, context: &RenderContext, _transform: &Transform) -> Result<()> {
+        let rotation = sky_rotation(context);
 
-                ..params.depth.clone()
-            },
-            // line_width: Some(0.1),
-            ..params.clone()
-        };
+        if let Some(panorama) = &self.panorama {
+            panorama.draw(target, context, &rotation)?;
+        }
 
         target.draw(
             &self.vertices,
             &self.indices,
-            &self.program,
+            &self.star_program.get(),
             &uniform! {
-                translation: identity!()
+                view: rotation,
+                projection: context.projection,
+                time: context.time,
+                twinkle: self.twinkle,
+                twinkle_speed: self.twinkle_speed,
             },
-            &params
-        ).unwrap();
+            // The soft edges of the stars add up over the panorama
+            &DrawParameters { blend: BlendMode::Additive.blend(), ..context.params.clone() }
+        )?;
 
+        let shooting_frame = (context.time * ANIMATION_SPEED) as i32 % ANIMATION_FRAMES;
         let translation_x = map_range(
             (0., ANIMATION_FRAMES as f32),
             (1.5, -1.5),
-            self.shooting_frame.next().unwrap() as f32
+            shooting_frame as f32
         );
         let translation_y = map_range(
             (0., ANIMATION_FRAMES as f32),
             (2.5, -1.),
-            self.shooting_frame.next().unwrap() as f32
+            shooting_frame as f32
         );
         let translation_matrix = translate!(translation_x, translation_y, 0.);
 
         target.draw(
             &self.shooting_stars,
             &self.shooting_indices,
-            &self.program,
+            &self.program.get(),
+            // Drawn straight on the screen
             &uniform! {
-                translation: translation_matrix
+                translation: translation_matrix,
+                view: identity!(),
+                projection: identity!(),
+                color: SHOOTING_STAR_COLOR,
             },
-            &params
-        ).unwrap();
+            &context.params
+        )?;
+        Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// View of the camera without its position, turning the sky with the camera.
+fn sky_rotation(context: &RenderContext) -> [[f32; 4]; 4] {
+    let mut rotation = Transform { view: context.camera, ..Default::default() }.get_view();
+    rotation[3] = [0.0, 0.0, 0.0, 1.0];
+    rotation
+}
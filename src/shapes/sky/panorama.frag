@@ -0,0 +1,28 @@
+#version 330
+
+// CUBEMAP samples a cubemap, otherwise an equirectangular panorama
+
+#ifdef CUBEMAP
+uniform samplerCube panorama;
+#else
+uniform sampler2D panorama;
+#endif
+
+in vec3 v_direction;
+out vec4 fragColor;
+
+const float PI = 3.14159265;
+
+void main() {
+    vec3 direction = normalize(v_direction);
+#ifdef CUBEMAP
+    vec3 color = texture(panorama, direction).rgb;
+#else
+    vec2 uv = vec2(
+        atan(direction.z, direction.x) / (2.0 * PI) + 0.5,
+        asin(clamp(direction.y, -1.0, 1.0)) / PI + 0.5
+    );
+    vec3 color = texture(panorama, uv).rgb;
+#endif
+    fragColor = vec4(color, 1.0);
+}
@@ -0,0 +1,131 @@
+use std::path::Path;
+use std::rc::Rc;
+
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::index::{NoIndices, PrimitiveType};
+use glium::texture::{CubeLayer, RawImage2d, SrgbCubemap, SrgbTexture2d};
+use glium::uniforms::{MagnifySamplerFilter, SamplerWrapFunction};
+use glium::{BlitTarget, Display, Surface, VertexBuffer};
+
+use crate::error::{Error, Result};
+use crate::render::{RenderContext, RenderTarget};
+use crate::shader;
+use crate::shapes::programs::{ProgramCache, ShaderProgram};
+use crate::shapes::Vertex;
+
+enum PanoramaTexture {
+    Equirectangular(SrgbTexture2d),
+    Cubemap(SrgbCubemap),
+}
+
+/// Picture of the whole sky around the camera, drawn behind the stars.
+pub struct Panorama {
+    texture: PanoramaTexture,
+    quad: VertexBuffer<Vertex>,
+    program: Rc<ShaderProgram>,
+}
+
+impl Panorama {
+    /// Loads a panorama with the longitude going right and the latitude going up,
+    /// twice as wide as it is high.
+    pub fn equirectangular(display: &Display, path: impl AsRef<Path>) -> Result<Self> {
+        let (pixels, dimensions) = load_rgba(path.as_ref())?;
+        // Flipped, so v = 0 is the bottom of the image, looking down
+        let image = RawImage2d::from_raw_rgba_reversed(&pixels, dimensions);
+        let texture = SrgbTexture2d::new(display, image)
+            .map_err(|e| Error::Texture(format!("Could not create the panorama {}: {e}", path.as_ref().display())))?;
+        Panorama::new(display, PanoramaTexture::Equirectangular(texture), &[])
+    }
+
+    /// Loads the six square faces of a cubemap, in the order +X, -X, +Y, -Y, +Z, -Z.
+    pub fn cubemap<P: AsRef<Path>>(display: &Display, faces: [P; 6]) -> Result<Self> {
+        let layers = [
+            CubeLayer::PositiveX,
+            CubeLayer::NegativeX,
+            CubeLayer::PositiveY,
+            CubeLayer::NegativeY,
+            CubeLayer::PositiveZ,
+            CubeLayer::NegativeZ,
+        ];
+
+        let mut cubemap = None;
+        for (face, layer) in faces.iter().zip(layers) {
+            let path = face.as_ref();
+            let error = |e: String| Error::Texture(format!("Could not create the cubemap face {}: {e}", path.display()));
+            let (pixels, (width, height)) = load_rgba(path)?;
+            if width != height {
+                return Err(error(format!("it is {width}x{height} pixels, not a square")));
+            }
+            let cubemap = match &cubemap {
+                Some(cubemap) => cubemap,
+                None => cubemap.insert(SrgbCubemap::empty(display, width).map_err(|e| error(e.to_string()))?),
+            };
+            if cubemap.width() != width {
+                return Err(error(format!("it is not as big as the first face, {} pixels wide", cubemap.width())));
+            }
+
+            // Not flipped, the faces of a cubemap start from their top row
+            let image = RawImage2d::from_raw_rgba(pixels, (width, height));
+            let face = SrgbTexture2d::new(display, image).map_err(|e| error(e.to_string()))?;
+            let source = SimpleFrameBuffer::new(display, &face).map_err(|e| error(format!("{e:?}")))?;
+            let target = SimpleFrameBuffer::new(display, cubemap.main_level().image(layer)).map_err(|e| error(format!("{e:?}")))?;
+            let whole = BlitTarget { left: 0, bottom: 0, width: width as i32, height: height as i32 };
+            source.blit_whole_color_to(&target, &whole, MagnifySamplerFilter::Linear);
+        }
+
+        let cubemap = cubemap.ok_or_else(|| Error::Texture("A cubemap needs six faces".to_string()))?;
+        Panorama::new(display, PanoramaTexture::Cubemap(cubemap), &[("CUBEMAP", "1")])
+    }
+
+    fn new(display: &Display, texture: PanoramaTexture, defines: &[(&'static str, &str)]) -> Result<Self> {
+        let corners = [[-1.0, -1.0], [1.0, -1.0], [-1.0, 1.0], [1.0, 1.0]].map(|[x, y]| Vertex::point(x, y, 0.0));
+        Ok(Panorama {
+            texture,
+            quad: VertexBuffer::new(display, &corners)?,
+            program: ProgramCache::for_display(display).get(shader!("panorama.vert"), shader!("panorama.frag"), defines)?,
+        })
+    }
+
+    /// Draws the panorama over the whole target, turned by the rotation of the camera.
+    pub(super) fn draw(&self, target: &mut RenderTarget, context: &RenderContext, rotation: &[[f32; 4]; 4]) -> Result<()> {
+        let indices = NoIndices(PrimitiveType::TriangleStrip);
+        let program = self.program.get();
+        match &self.texture {
+            PanoramaTexture::Equirectangular(texture) => target.draw(
+                &self.quad,
+                indices,
+                &program,
+                &uniform! {
+                    view: *rotation,
+                    projection: context.projection,
+                    // Wraps around at the longitude 180
+                    panorama: texture.sampled()
+                        .wrap_function(SamplerWrapFunction::Repeat)
+                        .magnify_filter(MagnifySamplerFilter::Linear),
+                },
+                &context.params,
+            )?,
+            PanoramaTexture::Cubemap(texture) => target.draw(
+                &self.quad,
+                indices,
+                &program,
+                &uniform! {
+                    view: *rotation,
+                    projection: context.projection,
+                    panorama: texture.sampled().magnify_filter(MagnifySamplerFilter::Linear),
+                },
+                &context.params,
+            )?,
+        }
+        Ok(())
+    }
+}
+
+/// RGBA pixels of an image file, from its top row down, with its size.
+fn load_rgba(path: &Path) -> Result<(Vec<u8>, (u32, u32))> {
+    let image = image::open(path)
+        .map_err(|e| Error::Texture(format!("Could not load texture {}: {e}", path.display())))?
+        .to_rgba8();
+    let dimensions = image.dimensions();
+    Ok((image.into_raw(), dimensions))
+}
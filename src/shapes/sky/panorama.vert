@@ -0,0 +1,17 @@
+#version 330
+
+// Corner of the screen, turned into the direction the camera looks at through it
+
+in vec3 position;
+
+uniform mat4 view;
+uniform mat4 projection;
+
+out vec3 v_direction;
+
+void main() {
+    vec4 direction = inverse(projection * view) * vec4(position.xy, 1.0, 1.0);
+    v_direction = direction.xyz / direction.w;
+    // At the far plane, behind everything else
+    gl_Position = vec4(position.xy, 1.0, 1.0);
+}
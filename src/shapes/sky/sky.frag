@@ -1,5 +1,7 @@
 #version 330
 
+uniform vec3 color;
+
 void main() {
-    gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+    gl_FragColor = vec4(color, 1.0);
 }
@@ -4,7 +4,9 @@ in vec3 position;
 
 uniform mat4 translation;
 uniform mat4 view;
+uniform mat4 projection;
 
 void main() {
-    gl_Position = view * translation * vec4(position, 1.0);
+    // At the far plane, behind everything else
+    gl_Position = (projection * view * translation * vec4(position, 1.0)).xyww;
 }
@@ -0,0 +1,16 @@
+#version 330
+
+in vec3 v_color;
+out vec4 fragColor;
+
+void main() {
+    // Squared distance from the center of the point sprite, 1 at its edge
+    vec2 coord = gl_PointCoord * 2.0 - 1.0;
+    float distance = dot(coord, coord);
+    if (distance > 1.0) {
+        discard;
+    }
+    // Bright core fading out to nothing at the edge
+    float falloff = exp(-4.0 * distance) * (1.0 - distance);
+    fragColor = vec4(v_color, falloff);
+}
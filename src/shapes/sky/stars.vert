@@ -0,0 +1,28 @@
+#version 330
+
+in vec3 position;
+in float size;
+in vec3 color;
+
+uniform mat4 view;
+uniform mat4 projection;
+uniform float time;
+// How much the brightness of the stars changes, 0 for steady stars
+uniform float twinkle;
+// Speed of the twinkle, in radians per second
+uniform float twinkle_speed;
+
+out vec3 v_color;
+
+void main() {
+    // At the far plane, behind everything else
+    gl_Position = (projection * view * vec4(position, 1.0)).xyww;
+    // Twice the size of the star, leaving room for its soft edge
+    gl_PointSize = size * 2.0;
+
+    // Each star twinkles out of step with the others, from a hash of its direction
+    float phase = fract(sin(dot(position, vec3(12.9898, 78.233, 37.719))) * 43758.5453) * 6.2831853;
+    float angle = time * twinkle_speed + phase;
+    float flicker = 0.6 * sin(angle) + 0.4 * sin(angle * 2.3 + phase);
+    v_color = color * max(1.0 + twinkle * flicker, 0.0);
+}
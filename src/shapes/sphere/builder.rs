@@ -1,16 +1,17 @@
 #![allow(dead_code)]
 
-use glium::Display;
-use crate::shapes::Filling;
+use std::rc::Rc;
 
-use crate::shapes::sphere::Sphere;
+use crate::shapes::mesh::{Material, MeshBuilder};
+use crate::shapes::sphere::{generate_vertices_and_indexes, SphereTopology};
+use crate::shapes::Vertex;
 
 /// Helps to create a sphere.
+/// The sphere is flat colored or textured, without lighting.
 pub struct SphereBuilder {
     radius: f32,
-    filling: Filling,
-    lats: usize,
-    longs: usize,
+    material: Material,
+    topology: SphereTopology,
 }
 
 impl SphereBuilder {
@@ -18,20 +19,23 @@ impl SphereBuilder {
     ///
     /// # Example
     /// ```no_run
-    /// use rt::shapes::SphereBuilder;
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::mesh::MeshBuilder;
+    /// use rt::shapes::sphere::SphereBuilder;
     /// let sphere = SphereBuilder::new()
     ///  .radius(1.0)
     ///  .color([1.0, 0.0, 0.0])
     ///  .lats(10)
     ///  .longs(10)
-    ///  .build(display);
+    ///  .build(display)?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn new() -> Self {
         SphereBuilder {
             radius: 1.0,
-            filling: Filling::Color([1.0, 0.0, 0.0]),
-            lats: 100,
-            longs: 100,
+            material: Material::unlit([1.0, 0.0, 0.0]),
+            topology: SphereTopology::default(),
         }
     }
 
@@ -43,29 +47,66 @@ impl SphereBuilder {
 
     /// Sets the color of the sphere.
     pub fn color(mut self, color: [f32; 3]) -> Self {
-        self.filling = Filling::Color(color);
+        self.material = Material::unlit(color);
         self
     }
 
+    /// Sets the texture of the sphere, mapped according to the topology.
     pub fn texture(mut self, texture: glium::texture::SrgbTexture2d) -> Self {
-        self.filling = Filling::Texture(texture);
+        self.material = Material {
+            texture: Some(Rc::new(texture)),
+            ..Material::unlit([1.0; 3])
+        };
         self
     }
 
-    /// Sets the number of latitudes.
+    /// Sets how the surface is split into triangles.
+    pub fn topology(mut self, topology: SphereTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Sets the number of latitudes, making it a UV sphere.
     pub fn lats(mut self, lats: usize) -> Self {
-        self.lats = lats;
+        self.topology = match self.topology {
+            SphereTopology::Uv { longs, .. } => SphereTopology::Uv { lats, longs },
+            _ => SphereTopology::Uv { lats, longs: lats },
+        };
         self
     }
 
-    /// Sets the number of longitudes.
+    /// Sets the number of longitudes, making it a UV sphere.
     pub fn longs(mut self, longs: usize) -> Self {
-        self.longs = longs;
+        self.topology = match self.topology {
+            SphereTopology::Uv { lats, .. } => SphereTopology::Uv { lats, longs },
+            _ => SphereTopology::Uv { lats: longs, longs },
+        };
         self
     }
 
-    /// Builds the sphere.
-    pub fn build(self, display: &Display) -> Sphere {
-        Sphere::new(display, self.radius, self.filling, self.lats, self.longs)
+    /// Makes it an icosphere, with its triangles split in four `subdivisions` times.
+    pub fn icosphere(self, subdivisions: usize) -> Self {
+        self.topology(SphereTopology::Icosphere { subdivisions })
+    }
+
+    /// Makes it a cube sphere, with each face split in a `subdivisions` x `subdivisions` grid.
+    pub fn cube_sphere(self, subdivisions: usize) -> Self {
+        self.topology(SphereTopology::CubeSphere { subdivisions })
+    }
+}
+
+impl Default for SphereBuilder {
+    fn default() -> Self {
+        SphereBuilder::new()
+    }
+}
+
+impl MeshBuilder for SphereBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        generate_vertices_and_indexes(self.radius, self.topology)
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
     }
 }
\ No newline at end of file
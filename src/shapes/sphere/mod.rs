@@ -1,150 +1,77 @@
-#[allow(mixed_script_confusables)]
-
-use glium::{Display, Surface, VertexBuffer};
-use glium::IndexBuffer;
-use glium::texture::*;
-
-use crate::shapes::{DynDrawble, Filling, Transform, Vertex};
-use crate::translate;
-
 mod builder;
+mod topology;
 pub use self::builder::SphereBuilder;
+pub use self::topology::SphereTopology;
+pub(crate) use self::topology::icosphere;
 
-/// Abstraction of a sphere.
-/// Creates a sphere with a given radius
-/// and a given number of subdivisions
-/// both in the x and y directions.
-pub struct Sphere {
-    vertices: VertexBuffer<Vertex>,
-    indices: IndexBuffer<u16>,
-    filling: Filling,
-    program: glium::Program,
-    pub radius: f32,
-}
+use crate::shapes::Vertex;
 
-impl Sphere {
-    pub fn radius(&self) -> f32 {
-        self.radius
+/// Generates the vertices and indexes of a sphere with the given topology.
+fn generate_vertices_and_indexes(radius: f32, topology: SphereTopology) -> (Vec<Vertex>, Vec<u32>) {
+    match topology {
+        SphereTopology::Uv { lats, longs } => uv_sphere(radius, lats, longs),
+        SphereTopology::Icosphere { subdivisions } => topology::icosphere(radius, subdivisions),
+        SphereTopology::CubeSphere { subdivisions } => topology::cube_sphere(radius, subdivisions),
     }
 }
 
-impl Sphere {
-    /// Creates a new sphere.
-    ///
-    /// # Example
-    /// ```no_run
-    /// use rt::shapes::Sphere;
-    /// let sphere = Sphere::new(1.0, Filling::Color([1.0, 0.0, 0.0]), 10, 10);
-    /// ```
-    pub fn new(display: &Display, radius: f32, filling: Filling, lats: usize, longs: usize) -> Self {
-        let (vertices, indices) = Self::generate_vertices_and_indexes(radius, lats, longs);
+/// Function that generates vertices and indexes for a UV sphere.
+///
+/// Latitudes go from the north to the south pole and longitudes go around the y axis,
+/// starting and ending at -z. The first column is repeated at the end with u = 1,
+/// so the texture does not wrap back over the whole sphere at the seam.
+/// Each pole row has one vertex per longitude, so its triangles form a fan whose
+/// texture coordinates follow the longitude they belong to.
+fn uv_sphere(radius: f32, lats: usize, longs: usize) -> (Vec<Vertex>, Vec<u32>) {
+    let lats = lats.max(2);
+    let longs = longs.max(3);
 
-        let vertex_buffer = glium::VertexBuffer::new(display, &vertices[..]).unwrap();
-        let index_buffer = glium::IndexBuffer::new(display, glium::index::PrimitiveType::TrianglesList, &indices[..]).unwrap();
+    let mut vertices = Vec::with_capacity((lats + 1) * (longs + 1));
+    let mut indices = Vec::with_capacity((lats - 1) * longs * 6);
 
-        let frag_shader = if let Filling::Color(_) = &filling {
-            include_str!("sphere.frag")
-        } else {
-            include_str!("sphere_texture.frag")
-        };
+    let lat_step = std::f32::consts::PI / lats as f32;
+    let long_step = std::f32::consts::PI * 2.0 / longs as f32;
 
-        Sphere {
-            radius,
-            vertices: vertex_buffer,
-            indices: index_buffer,
-            filling,
-            program: glium::Program::from_source(
-                display,
-                include_str!("sphere.vert"),
-                frag_shader,
-                None,
-            ).unwrap(),
-        }
-    }
-
-    /// Function that generates vertices and indexes for a sphere.
-    fn generate_vertices_and_indexes(radius: f32, lats: usize, longs: usize) -> (Vec<Vertex>, Vec<u16>) {
-        let mut vertices = Vec::with_capacity(lats * longs);
-        let mut indices = Vec::with_capacity(lats * longs * 6);
+    for lat in 0..=lats {
+        let theta = lat_step * lat as f32;
+        let v = 1.0 - lat as f32 / lats as f32;
 
-        let lat_step = std::f32::consts::PI * 2.0 / lats as f32;
-        let long_step = std::f32::consts::PI * 2.0 / longs as f32;
+        // Pole vertices sit in the middle of the longitude they close
+        let long_offset = if lat == 0 || lat == lats { 0.5 } else { 0.0 };
 
-        for lat in 0..lats {
-            let θ = lat_step * lat as f32;
-            let y = radius * θ.sin();
-            let h = radius * θ.cos();
-
-            // Do not duplicate the poles
-            if lat == 0 || lat == lats - 1 {
-                vertices.push(Vertex::new(0.0, y, 0.0));
-                continue;
-            }
+        for long in 0..=longs {
+            let long = long as f32 + long_offset;
+            let phi = long_step * long - std::f32::consts::PI;
+            let u = long / longs as f32;
 
-            for long in 0..longs {
-                let φ = long_step * long as f32;
-                let x = h * φ.sin();
-                let z = h * φ.cos();
+            let normal = [theta.sin() * phi.sin(), theta.cos(), theta.sin() * phi.cos()];
+            let position = normal.map(|n| n * radius);
+            let tangent = [phi.cos(), 0.0, -phi.sin()];
 
-
-                vertices.push(Vertex::new(x, y, z));
-
-                // Make indices list
-                if lat != lats - 1 && long != longs - 1 {
-
-                    let long = long as u16;
-                    let longs = longs as u16;
-                    let lat = lat as u16;
-
-                    indices.append(&mut vec![
-                        long + lat * longs,
-                        (long + 1) + lat * longs,
-                        long + (lat + 1) * longs
-                    ]);
-                    indices.append(&mut vec![
-                        (long + 1) + lat * longs,
-                        (long + 1) + (lat + 1) * longs,
-                        long + (lat + 1) * longs
-                    ]);
-                }
-            }
+            vertices.push(Vertex::new(position, normal, tangent, [u, v]));
         }
-        (vertices, indices)
     }
-}
 
-impl DynDrawble for Sphere {
-    /// Draws the sphere.
-    fn draw(&self, target: &mut glium::Frame, params: &glium::DrawParameters, transform: Transform) {
-        let uniforms = uniform! {
-                translation: transform.get_translation(),
-                undo_translation: translate!(-transform.translation[0], -transform.translation[1], -transform.translation[2]),
-                scale: transform.get_scaling(),
-                rotation: transform.get_rotation(),
-                self_rotation: transform.get_self_rotation(),
-                view: transform.get_view(),
-            };
-        match &self.filling {
-            Filling::Color(color) => {
-                let uniforms = uniforms.add("color", [color[0], color[1], color[2]]);
-                target.draw(
-                    &self.vertices,
-                    &self.indices,
-                    &self.program,
-                    &uniforms,
-                    &params,
-                ).unwrap()
-            },
-            Filling::Texture(texture) => {
-                let uniforms = uniforms.add("texture", texture.clone());
-                target.draw(
-                    &self.vertices,
-                    &self.indices,
-                    &self.program,
-                    &uniforms,
-                    &params,
-                ).unwrap()
-            },
+    let row = longs as u32 + 1;
+    for lat in 0..lats as u32 {
+        for long in 0..longs as u32 {
+            let top_left = lat * row + long;
+            let top_right = top_left + 1;
+            let bottom_left = top_left + row;
+            let bottom_right = bottom_left + 1;
+
+            if lat == 0 {
+                // Fan around the north pole
+                indices.extend_from_slice(&[top_left, bottom_left, bottom_right]);
+            } else if lat == lats as u32 - 1 {
+                // Fan around the south pole
+                indices.extend_from_slice(&[top_left, bottom_left, top_right]);
+            } else {
+                indices.extend_from_slice(&[top_left, bottom_left, bottom_right]);
+                indices.extend_from_slice(&[top_left, bottom_right, top_right]);
+            }
         }
     }
+
+    (vertices, indices)
 }
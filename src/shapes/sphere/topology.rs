@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use crate::shapes::cube::FACES;
+use crate::shapes::vectors::{dot, normalize, scale, sub};
+use crate::shapes::Vertex;
+
+/// How the surface of a sphere is split into triangles.
+///
+/// The normal of every vertex is its direction from the center,
+/// so it can also be used to sample a cube map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SphereTopology {
+    /// Grid of latitudes and longitudes, mapped to an equirectangular texture.
+    /// Triangles get denser towards the poles.
+    Uv { lats: usize, longs: usize },
+    /// Icosahedron with every triangle split in four `subdivisions` times,
+    /// mapped to the same cube map atlas as `CubeSphere`.
+    Icosphere { subdivisions: usize },
+    /// Cube with every face split in a `subdivisions` x `subdivisions` grid and pushed onto the sphere.
+    /// Each face covers one cell of a 3 x 2 cube map atlas, ordered
+    /// right, left, top in the bottom row and bottom, front, back in the top row.
+    CubeSphere { subdivisions: usize },
+}
+
+impl Default for SphereTopology {
+    fn default() -> Self {
+        SphereTopology::Uv { lats: 100, longs: 100 }
+    }
+}
+
+/// Generates the vertices and indexes of an icosphere.
+///
+/// Each triangle is mapped on the face of the cube its center points to, so the vertices
+/// shared by triangles of different faces are repeated once per face.
+pub(crate) fn icosphere(radius: f32, subdivisions: usize) -> (Vec<Vertex>, Vec<u32>) {
+    let (directions, triangles) = icosphere_directions(subdivisions);
+
+    let mut vertices = Vec::with_capacity(directions.len());
+    let mut indices = Vec::with_capacity(triangles.len() * 3);
+    // Copies of the vertices, by original index and face of the cube
+    let mut copies = HashMap::new();
+
+    for triangle in triangles {
+        let center = triangle.iter()
+            .fold([0.0; 3], |sum, &index| [0, 1, 2].map(|k| sum[k] + directions[index as usize][k]));
+        let face = cube_face(center);
+
+        for index in triangle {
+            let copy = *copies.entry((index, face)).or_insert_with(|| {
+                vertices.push(cube_map_vertex(radius, directions[index as usize], face));
+                vertices.len() as u32 - 1
+            });
+            indices.push(copy);
+        }
+    }
+
+    (vertices, indices)
+}
+
+/// Generates the directions of the vertices of an icosphere and its counter-clockwise triangles.
+pub(crate) fn icosphere_directions(subdivisions: usize) -> (Vec<[f32; 3]>, Vec<[u32; 3]>) {
+    let t = (1.0 + 5f32.sqrt()) / 2.0;
+
+    let mut directions: Vec<[f32; 3]> = [
+        [-1.0, t, 0.0], [1.0, t, 0.0], [-1.0, -t, 0.0], [1.0, -t, 0.0],
+        [0.0, -1.0, t], [0.0, 1.0, t], [0.0, -1.0, -t], [0.0, 1.0, -t],
+        [t, 0.0, -1.0], [t, 0.0, 1.0], [-t, 0.0, -1.0], [-t, 0.0, 1.0],
+    ].iter().map(|&p| normalize(p)).collect();
+
+    let mut triangles: Vec<[u32; 3]> = vec![
+        [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+        [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+        [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+        [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        // Vertex created in the middle of each edge, so neighbouring triangles share it
+        let mut midpoints = HashMap::new();
+        let mut midpoint = |a: u32, b: u32| -> u32 {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                let (pa, pb) = (directions[a as usize], directions[b as usize]);
+                directions.push(normalize([pa[0] + pb[0], pa[1] + pb[1], pa[2] + pb[2]]));
+                directions.len() as u32 - 1
+            })
+        };
+
+        triangles = triangles.iter()
+            .flat_map(|&[a, b, c]| {
+                let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+
+    (directions, triangles)
+}
+
+/// Generates the vertices and indexes of a cube sphere.
+///
+/// The grid of each face is spaced by equal angles instead of equal distances on the cube,
+/// which keeps the triangles close to the same size once pushed onto the sphere.
+pub(crate) fn cube_sphere(radius: f32, subdivisions: usize) -> (Vec<Vertex>, Vec<u32>) {
+    let subdivisions = subdivisions.max(1);
+    let row = subdivisions as u32 + 1;
+
+    let mut vertices = Vec::with_capacity(6 * (row * row) as usize);
+    let mut indices = Vec::with_capacity(6 * subdivisions * subdivisions * 6);
+
+    for (face, [normal, tangent, bitangent]) in FACES.iter().enumerate() {
+        let (column, atlas_row) = ((face % 3) as f32, (face / 3) as f32);
+        let first = vertices.len() as u32;
+
+        for j in 0..row {
+            for i in 0..row {
+                let (a, b) = (i as f32 / subdivisions as f32, j as f32 / subdivisions as f32);
+                let s = (PI / 4.0 * (2.0 * a - 1.0)).tan();
+                let t = (PI / 4.0 * (2.0 * b - 1.0)).tan();
+
+                let direction = normalize([0, 1, 2].map(|k| normal[k] + s * tangent[k] + t * bitangent[k]));
+                // The face tangent, projected onto the sphere
+                let surface_tangent = normalize(sub(*tangent, scale(direction, dot(*tangent, direction))));
+
+                vertices.push(Vertex::new(
+                    scale(direction, radius),
+                    direction,
+                    surface_tangent,
+                    [(column + a) / 3.0, (atlas_row + b) / 2.0],
+                ));
+            }
+        }
+
+        for j in 0..row - 1 {
+            for i in 0..row - 1 {
+                let bottom_left = first + j * row + i;
+                let bottom_right = bottom_left + 1;
+                let top_left = bottom_left + row;
+                let top_right = top_left + 1;
+                indices.extend_from_slice(&[bottom_left, bottom_right, top_right]);
+                indices.extend_from_slice(&[bottom_left, top_right, top_left]);
+            }
+        }
+    }
+
+    (vertices, indices)
+}
+
+/// Face of the cube, as an index in `FACES`, a direction points to.
+fn cube_face(direction: [f32; 3]) -> usize {
+    (0..FACES.len())
+        .max_by(|&a, &b| dot(FACES[a][0], direction).total_cmp(&dot(FACES[b][0], direction)))
+        .unwrap_or(0)
+}
+
+/// Vertex of the sphere in a direction, mapped on a face of the cube like the cube sphere:
+/// by the angles from the center of the face, in its cell of the 3 x 2 atlas.
+fn cube_map_vertex(radius: f32, direction: [f32; 3], face: usize) -> Vertex {
+    let [normal, tangent, bitangent] = FACES[face];
+    let (column, atlas_row) = ((face % 3) as f32, (face / 3) as f32);
+
+    // Vertices of a triangle crossing an edge of the cube can point a little past it,
+    // they are kept on the edge of the cell
+    let depth = dot(normal, direction).max(1e-3);
+    let coordinate = |axis: [f32; 3]| {
+        let angle = (dot(axis, direction) / depth).atan();
+        ((angle / (PI / 4.0) + 1.0) / 2.0).clamp(0.0, 1.0)
+    };
+    let (a, b) = (coordinate(tangent), coordinate(bitangent));
+    let surface_tangent = normalize(sub(tangent, scale(direction, dot(tangent, direction))));
+
+    Vertex::new(
+        scale(direction, radius),
+        direction,
+        surface_tangent,
+        [(column + a) / 3.0, (atlas_row + b) / 2.0],
+    )
+}
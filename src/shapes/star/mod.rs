@@ -1,81 +1,69 @@
-use glium::{Display, DrawParameters, Frame, Program, Surface, VertexBuffer};
-use glium::index::NoIndices;
-use crate::{DynDrawble, Transform};
+use std::rc::Rc;
+
+use glium::Display;
+use glium::index::PrimitiveType;
+use crate::Transform;
+use crate::error::Result;
+use crate::render::{Drawable, RenderContext, RenderTarget};
 use crate::shapes::Vertex;
-use crate::shapes::triangle::{Triangle, TriangleBuilder};
+use crate::shapes::mesh::{Material, Mesh, MeshBuilder};
+use crate::shapes::triangle::TriangleBuilder;
+
+/// Colors of the star, brighter than white so it glows through the bloom of an HDR target.
+const TRIANGLE_COLOR: [f32; 3] = [4.0, 3.6, 2.8];
+const SHINE_COLOR: [f32; 3] = [3.0, 2.7, 2.1];
 
+/// Six pointed star: a triangle drawn twice, the second one turned by 60 degrees, and its shine.
 pub struct Star {
-    triangles: Vec<Triangle>,
-    pub shine_vertices: VertexBuffer<Vertex>,
-    pub shine_index: NoIndices,
-    pub shine_program: Program,
+    triangle: Mesh,
+    shine: Mesh,
 }
 
 impl Star {
-    pub fn new(display: &Display) -> Self {
-        let triangles = (0..2).map(|_| TriangleBuilder::new().color([1.0; 3]).build(display)).collect();
+    pub fn new(display: &Display) -> Result<Self> {
+        let triangle = TriangleBuilder::new().color(TRIANGLE_COLOR).build(display)?;
 
         let shine_points = (0..360)
             .step_by(60)
             .map(|angle| (angle as f32).to_radians())
-            .map(|angle| {
-                let x = (angle.cos());
-                let y = (angle.sin());
-                let x1 = (angle.cos() * 1.2);
-                let y1 = (angle.sin() * 1.2);
+            .flat_map(|angle| {
+                let x = angle.cos();
+                let y = angle.sin();
+                let x1 = angle.cos() * 1.2;
+                let y1 = angle.sin() * 1.2;
                 [
-                    Vertex::new(x, y, 0.0),
-                    Vertex::new(x1, y1, 0.0),
+                    Vertex::point(x, y, 0.0),
+                    Vertex::point(x1, y1, 0.0),
                 ]
             })
-            .flatten()
             .collect::<Vec<Vertex>>();
+        let shine_indices = (0..shine_points.len() as u32).collect::<Vec<_>>();
 
-        let shine_vertices = glium::VertexBuffer::new(display, &shine_points).unwrap();
-
-        let shine_index = glium::index::NoIndices(glium::index::PrimitiveType::LinesList);
-
-        let shine_program = glium::Program::from_source(
+        let shine = Mesh::with_primitive(
             display,
-            include_str!("../sphere/sphere.vert"),
-            include_str!("../sky/sky.frag"),
-            None
-        ).unwrap();
+            &shine_points,
+            &shine_indices,
+            PrimitiveType::LinesList,
+            Rc::new(Material::unlit(SHINE_COLOR)),
+        )?;
 
-        Star {
-            triangles,
-            shine_vertices,
-            shine_index,
-            shine_program,
-        }
+        Ok(Star {
+            triangle,
+            shine,
+        })
     }
 }
 
-impl DynDrawble for Star {
-    fn draw(&self, target: &mut Frame, params: &DrawParameters, transform: Transform) {
-        let uniforms = uniform! {
-            translation: transform.get_translation(),
-            rotation: transform.get_rotation(),
-            scale: transform.get_scaling(),
-            self_rotation: transform.get_self_rotation(),
-            view: transform.get_view()
-        };
-
+impl Drawable for Star {
+    fn draw(&self, target: &mut RenderTarget, context: &RenderContext, transform: &Transform) -> Result<()> {
         let rotate_self = transform.rotate_self;
         let second_triangle_transform = Transform {
             rotate_self: [rotate_self[0], rotate_self[1], (rotate_self[2] + 60f32.to_radians())],
             ..transform.clone()
         };
 
-        self.triangles[0].draw(target, params, transform);
-        self.triangles[1].draw(target, params, second_triangle_transform);
-
-        target.draw(
-            &self.shine_vertices,
-            &self.shine_index,
-            &self.shine_program,
-            &uniforms,
-            params
-        ).unwrap();
+        self.triangle.draw(target, context, transform)?;
+        self.triangle.draw(target, context, &second_triangle_transform)?;
+        self.shine.draw(target, context, transform)
     }
-}
\ No newline at end of file
+}
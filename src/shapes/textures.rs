@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+
+use glium::Display;
+use glium::texture::{RawImage2d, SrgbTexture2d};
+
+use crate::error::{Error, Result};
+
+/// Keeps the loaded textures by name, so each one is uploaded only once
+/// and shared by every material using it.
+#[derive(Default)]
+pub struct TextureManager {
+    textures: HashMap<String, Rc<SrgbTexture2d>>,
+}
+
+impl TextureManager {
+    pub fn new() -> Self {
+        TextureManager::default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<Rc<SrgbTexture2d>> {
+        self.textures.get(name).cloned()
+    }
+
+    /// Loads an image file, or gives back the texture already loaded from it.
+    pub fn load_file(&mut self, display: &Display, path: &Path) -> Result<Rc<SrgbTexture2d>> {
+        let name = path.display().to_string();
+        if let Some(texture) = self.get(&name) {
+            return Ok(texture);
+        }
+
+        let image = image::open(path)
+            .map_err(|e| Error::Texture(format!("Could not load texture {name}: {e}")))?
+            .to_rgba8();
+        let dimensions = image.dimensions();
+        self.load_rgba(display, name, image.into_raw(), dimensions)
+    }
+
+    /// Uploads RGBA pixels, listed from the top row down, under a name.
+    /// Gives back the texture already uploaded under that name, if any.
+    pub fn load_rgba(
+        &mut self,
+        display: &Display,
+        name: String,
+        pixels: Vec<u8>,
+        dimensions: (u32, u32),
+    ) -> Result<Rc<SrgbTexture2d>> {
+        if let Some(texture) = self.get(&name) {
+            return Ok(texture);
+        }
+
+        // Flipped, so v = 0 is the bottom of the image like in the other textures
+        let image = RawImage2d::from_raw_rgba_reversed(&pixels, dimensions);
+        let texture = SrgbTexture2d::new(display, image)
+            .map_err(|e| Error::Texture(format!("Could not create texture {name}: {e}")))?;
+
+        let texture = Rc::new(texture);
+        self.textures.insert(name, Rc::clone(&texture));
+        Ok(texture)
+    }
+}
@@ -0,0 +1,87 @@
+#![allow(dead_code)]
+
+use crate::shapes::mesh::{Material, MeshBuilder};
+use crate::shapes::torus::generate_vertices_and_indexes;
+use crate::shapes::Vertex;
+
+/// Helps to create a torus around the y axis.
+pub struct TorusBuilder {
+    radius: f32,
+    tube_radius: f32,
+    segments: usize,
+    sides: usize,
+    color: [f32; 3],
+}
+
+impl TorusBuilder {
+    /// Creates a new torus builder.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(display: &glium::Display) -> rt::Result<()> {
+    /// use rt::shapes::mesh::MeshBuilder;
+    /// use rt::shapes::torus::TorusBuilder;
+    /// let habitat_ring = TorusBuilder::new()
+    ///  .radius(1.0)
+    ///  .tube_radius(0.1)
+    ///  .segments(64)
+    ///  .build(display)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new() -> Self {
+        TorusBuilder {
+            radius: 1.0,
+            tube_radius: 0.25,
+            segments: 48,
+            sides: 16,
+            color: [0.8, 0.8, 0.8],
+        }
+    }
+
+    /// Sets the distance from the center to the middle of the tube.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the radius of the tube.
+    pub fn tube_radius(mut self, tube_radius: f32) -> Self {
+        self.tube_radius = tube_radius;
+        self
+    }
+
+    /// Sets the number of steps around the y axis.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the number of steps around the tube.
+    pub fn sides(mut self, sides: usize) -> Self {
+        self.sides = sides;
+        self
+    }
+
+    /// Sets the color of the torus.
+    pub fn color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Default for TorusBuilder {
+    fn default() -> Self {
+        TorusBuilder::new()
+    }
+}
+
+impl MeshBuilder for TorusBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        generate_vertices_and_indexes(self.radius, self.tube_radius, self.segments, self.sides)
+    }
+
+    fn material(&self) -> Material {
+        Material::color(self.color)
+    }
+}
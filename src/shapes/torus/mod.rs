@@ -0,0 +1,23 @@
+use std::f32::consts::TAU;
+
+use crate::shapes::Vertex;
+use crate::shapes::revolution::{revolve, ProfilePoint};
+
+mod builder;
+pub use self::builder::TorusBuilder;
+
+/// Function that generates vertices and indexes for a torus lying on the y = 0 plane.
+///
+/// u goes around the y axis and v around the tube, starting from its outer edge and going up.
+pub(crate) fn generate_vertices_and_indexes(radius: f32, tube_radius: f32, segments: usize, sides: usize) -> (Vec<Vertex>, Vec<u32>) {
+    let sides = sides.max(3);
+    let profile: Vec<ProfilePoint> = (0..=sides)
+        .map(|side| {
+            let v = side as f32 / sides as f32;
+            let (sin, cos) = (v * TAU).sin_cos();
+            ProfilePoint::new(radius + tube_radius * cos, tube_radius * sin, [cos, sin], v)
+        })
+        .collect();
+
+    revolve(&profile, segments)
+}
@@ -1,5 +1,6 @@
-use glium::Display;
-use crate::shapes::triangle::Triangle;
+use crate::shapes::mesh::{Material, MeshBuilder};
+use crate::shapes::triangle::{generate_indices, generate_vertices};
+use crate::shapes::Vertex;
 
 pub struct TriangleBuilder {
     points: [[f32; 2]; 3],
@@ -32,8 +33,20 @@ impl TriangleBuilder {
         self.color = color;
         self
     }
+}
+
+impl Default for TriangleBuilder {
+    fn default() -> Self {
+        TriangleBuilder::new()
+    }
+}
+
+impl MeshBuilder for TriangleBuilder {
+    fn geometry(&self) -> (Vec<Vertex>, Vec<u32>) {
+        (generate_vertices(&self.points), generate_indices(&self.points))
+    }
 
-    pub fn build(&self, display: &Display) -> Triangle {
-        Triangle::new(display, &self.points, self.color)
+    fn material(&self) -> Material {
+        Material::unlit(self.color)
     }
 }
\ No newline at end of file
@@ -0,0 +1,133 @@
+//! Options of the window and of its OpenGL context, from a scene file or the command line.
+
+use glium::glutin;
+use glium::glutin::event_loop::EventLoop;
+use glium::Display;
+
+use crate::error::{Error, Result};
+use crate::scene::SceneFile;
+
+/// How the window and its OpenGL context are created.
+///
+/// ```
+/// let scene = rt::SceneFile::parse("window.samples = 8").unwrap();
+/// let options = rt::WindowOptions::from_scene(&scene).unwrap()
+///     .with_args(["--size", "800x600", "--no-vsync"].map(String::from))
+///     .unwrap();
+/// assert_eq!(options.samples, 8);
+/// assert_eq!(options.size, (800, 600));
+/// assert!(!options.vsync);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowOptions {
+    pub title: String,
+    /// Inner size of the window, in logical pixels
+    pub size: (u32, u32),
+    /// Samples of the multisampling, a power of two, or 0 for none
+    pub samples: u16,
+    /// Waits for the screen to refresh before showing a frame
+    pub vsync: bool,
+    /// Converts the colors drawn on the screen to sRGB
+    pub srgb: bool,
+    pub depth_bits: u8,
+}
+
+impl Default for WindowOptions {
+    fn default() -> Self {
+        WindowOptions {
+            title: "OpenGL Space Scene".to_string(),
+            size: (400, 400),
+            samples: 4,
+            vsync: true,
+            srgb: true,
+            depth_bits: 24,
+        }
+    }
+}
+
+impl WindowOptions {
+    /// Reads the `window.*` keys of a scene file, keeping the default of the others.
+    pub fn from_scene(scene: &SceneFile) -> Result<Self> {
+        let mut options = WindowOptions::default();
+        for (key, value) in scene.group("window") {
+            options.set(key, value).map_err(|e| match e {
+                Error::Window(message) => Error::Scene(format!("window.{message}")),
+                e => e,
+            })?;
+        }
+        Ok(options)
+    }
+
+    /// Changes the options given on the command line:
+    /// `--title <title>`, `--size <width>x<height>`, `--samples <n>`, `--depth-bits <n>`,
+    /// `--vsync`, `--no-vsync`, `--srgb` and `--no-srgb`.
+    pub fn with_args(mut self, args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                return Err(Error::Window(format!("Unexpected argument {arg}")));
+            };
+            match flag {
+                "vsync" | "srgb" => self.set(flag, "true")?,
+                "no-vsync" | "no-srgb" => self.set(&flag[3..], "false")?,
+                _ => {
+                    let value = args.next().ok_or_else(|| Error::Window(format!("--{flag} needs a value")))?;
+                    let key = flag.replace('-', "_");
+                    self.set(&key, &value).map_err(|e| match e {
+                        Error::Window(message) => Error::Window(message.replacen(&key, &arg, 1)),
+                        e => e,
+                    })?;
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Changes an option by its name in scene files.
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let invalid = || Error::Window(format!("{key} can't be {value}"));
+        match key {
+            "title" => self.title = value.to_string(),
+            "size" => {
+                let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+                self.size = (width.trim().parse().map_err(|_| invalid())?, height.trim().parse().map_err(|_| invalid())?);
+            }
+            "samples" => {
+                let samples: u16 = value.parse().map_err(|_| invalid())?;
+                if samples > 1 && !samples.is_power_of_two() {
+                    return Err(invalid());
+                }
+                self.samples = samples;
+            }
+            "vsync" => self.vsync = value.parse().map_err(|_| invalid())?,
+            "srgb" => self.srgb = value.parse().map_err(|_| invalid())?,
+            "depth_bits" => self.depth_bits = value.parse().map_err(|_| invalid())?,
+            _ => return Err(Error::Window(format!("{key} is not an option of the window"))),
+        }
+        Ok(())
+    }
+
+    /// Opens the window, with half the samples each time the GPU can't give as many as asked.
+    ///
+    /// Gives back the samples the window got, 0 without multisampling,
+    /// for the caller to tell when it got fewer than `samples`.
+    pub fn open(&self, event_loop: &EventLoop<()>) -> Result<(Display, u16)> {
+        let mut samples = self.samples;
+        loop {
+            let window = glutin::window::WindowBuilder::new()
+                .with_title(&self.title)
+                .with_inner_size(glutin::dpi::LogicalSize::new(self.size.0, self.size.1));
+            let context = glutin::ContextBuilder::new()
+                .with_depth_buffer(self.depth_bits)
+                .with_vsync(self.vsync)
+                .with_srgb(self.srgb)
+                .with_multisampling(samples);
+
+            match Display::new(window, context, event_loop) {
+                Ok(display) => return Ok((display, samples)),
+                Err(_) if samples > 0 => samples /= 2,
+                Err(e) => return Err(Error::Window(e.to_string())),
+            }
+        }
+    }
+}